@@ -0,0 +1,76 @@
+use crate::{
+    error::SerializerError, float::write_shortest, parser::JsonValue, serializer::write_string,
+};
+use std::io;
+
+/// Appends `value` the way ECMAScript's `Number.prototype.toString` prints
+/// it, as RFC 8785 requires.
+fn write_es_number(out: &mut String, value: f64) -> Result<(), SerializerError> {
+    if !value.is_finite() {
+        return Err(SerializerError::NonFiniteNumber(value));
+    }
+    if value == 0.0 {
+        // ECMAScript prints negative zero as `0`
+        out.push('0');
+        return Ok(());
+    }
+    write_shortest(out, value);
+    Ok(())
+}
+
+fn write_value(out: &mut String, value: &JsonValue) -> Result<(), SerializerError> {
+    match *value {
+        JsonValue::Object(ref object) => {
+            let mut members: Vec<(&String, &JsonValue)> = object.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            // sorting puts repeated keys next to each other
+            if let Some(pair) = members.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+                return Err(SerializerError::DuplicateKey(pair[0].0.clone()));
+            }
+            out.push('{');
+            for (index, (key, value)) in members.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, value)?;
+            }
+            out.push('}');
+        }
+        JsonValue::Array(ref array) => {
+            out.push('[');
+            for (index, value) in array.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(out, value)?;
+            }
+            out.push(']');
+        }
+        JsonValue::String(ref string) => write_string(out, string),
+        JsonValue::Number(ref number) => write_es_number(out, number.as_f64())?,
+        JsonValue::Boolean(boolean) => out.push_str(if boolean { "true" } else { "false" }),
+        JsonValue::Null => out.push_str("null"),
+    }
+    Ok(())
+}
+
+impl JsonValue {
+    /// Serializes the value in the JSON Canonicalization Scheme of RFC 8785:
+    /// object keys sorted by UTF-16 code units, numbers as IEEE 754 doubles
+    /// in ECMAScript notation, minimal string escaping and no whitespace.
+    /// The output is byte-stable and suitable for signing. Objects that
+    /// repeat a key are refused, as RFC 8785 requires unique member names.
+    pub fn to_canonical_string(&self) -> Result<String, SerializerError> {
+        let mut out = String::new();
+        write_value(&mut out, self)?;
+        Ok(out)
+    }
+
+    pub fn write_canonical<W: io::Write>(&self, writer: &mut W) -> Result<(), SerializerError> {
+        let json = self.to_canonical_string()?;
+        writer.write_all(json.as_bytes())?;
+        Ok(())
+    }
+}
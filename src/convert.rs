@@ -0,0 +1,253 @@
+use crate::{error::ParsedJsonError, number::JsonNumber, object::JsonObject, parser::JsonValue};
+use std::collections::{BTreeMap, HashMap};
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Boolean(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+impl From<JsonNumber> for JsonValue {
+    fn from(value: JsonNumber) -> Self {
+        JsonValue::Number(value)
+    }
+}
+
+impl From<JsonObject> for JsonValue {
+    fn from(value: JsonObject) -> Self {
+        JsonValue::Object(value)
+    }
+}
+
+macro_rules! from_integer {
+    ($wide:ty => $($ty:ty),*) => {
+        $(
+            impl From<$ty> for JsonValue {
+                fn from(value: $ty) -> Self {
+                    JsonValue::Number(JsonNumber::from(value as $wide))
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(u64 => u8, u16, u32, u64, usize);
+from_integer!(i64 => i8, i16, i32, i64, isize);
+
+macro_rules! from_wide_integer {
+    ($($ty:ty),*) => {
+        $(
+            /// Values beyond 64 bits keep their exact decimal text.
+            impl From<$ty> for JsonValue {
+                fn from(value: $ty) -> Self {
+                    let number = match (u64::try_from(value), i64::try_from(value)) {
+                        (Ok(value), _) => JsonNumber::from(value),
+                        (_, Ok(value)) => JsonNumber::from(value),
+                        _ => JsonNumber::from_lexeme(value.to_string()),
+                    };
+                    JsonValue::Number(number)
+                }
+            }
+        )*
+    };
+}
+
+from_wide_integer!(u128, i128);
+
+impl From<f32> for JsonValue {
+    fn from(value: f32) -> Self {
+        JsonValue::Number(JsonNumber::from(value as f64))
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        JsonValue::Number(JsonNumber::from(value))
+    }
+}
+
+/// `None` becomes `Null`.
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(value: Vec<T>) -> Self {
+        JsonValue::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Clone + Into<JsonValue>> From<&[T]> for JsonValue {
+    fn from(value: &[T]) -> Self {
+        JsonValue::Array(value.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<JsonValue>, const N: usize> From<[T; N]> for JsonValue {
+    fn from(value: [T; N]) -> Self {
+        JsonValue::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Members follow the map's iteration order, which is arbitrary for a
+/// `HashMap`.
+impl<T: Into<JsonValue>> From<HashMap<String, T>> for JsonValue {
+    fn from(value: HashMap<String, T>) -> Self {
+        JsonValue::Object(value.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl<T: Into<JsonValue>> From<BTreeMap<String, T>> for JsonValue {
+    fn from(value: BTreeMap<String, T>) -> Self {
+        JsonValue::Object(value.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl TryFrom<&JsonValue> for bool {
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value.get_boolean()
+    }
+}
+
+impl<'a> TryFrom<&'a JsonValue> for &'a str {
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &'a JsonValue) -> Result<Self, Self::Error> {
+        value.get_string()
+    }
+}
+
+impl TryFrom<&JsonValue> for String {
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value.get_string().map(str::to_string)
+    }
+}
+
+macro_rules! try_from_integer {
+    ($($ty:ty),*) => {
+        $(
+            /// Fails with `NumberOutOfRange` unless the number is an integer
+            /// that fits.
+            impl TryFrom<&JsonValue> for $ty {
+                type Error = ParsedJsonError;
+
+                fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+                    let number = match *value {
+                        JsonValue::Number(ref number) => number,
+                        _ => return Err(value.unexpected_type("number")),
+                    };
+                    let converted = match number.as_str_lossless() {
+                        Some(lexeme) => lexeme.parse().ok(),
+                        None => number
+                            .as_i64()
+                            .and_then(|value| <$ty>::try_from(value).ok())
+                            .or_else(|| number.as_u64().and_then(|value| <$ty>::try_from(value).ok())),
+                    };
+                    converted.ok_or_else(|| {
+                        ParsedJsonError::NumberOutOfRange(number.to_string(), stringify!($ty))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+try_from_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl TryFrom<&JsonValue> for f64 {
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value.get_number()
+    }
+}
+
+/// Rounds to the nearest `f32`.
+impl TryFrom<&JsonValue> for f32 {
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value.get_number().map(|value| value as f32)
+    }
+}
+
+/// `Null` becomes `None`.
+impl<T> TryFrom<&JsonValue> for Option<T>
+where
+    T: for<'a> TryFrom<&'a JsonValue, Error = ParsedJsonError>,
+{
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        match *value {
+            JsonValue::Null => Ok(None),
+            _ => T::try_from(value).map(Some),
+        }
+    }
+}
+
+impl<T> TryFrom<&JsonValue> for Vec<T>
+where
+    T: for<'a> TryFrom<&'a JsonValue, Error = ParsedJsonError>,
+{
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value
+            .as_array()
+            .ok_or_else(|| value.unexpected_type("array"))?
+            .iter()
+            .map(T::try_from)
+            .collect()
+    }
+}
+
+impl<T> TryFrom<&JsonValue> for HashMap<String, T>
+where
+    T: for<'a> TryFrom<&'a JsonValue, Error = ParsedJsonError>,
+{
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value
+            .as_object()
+            .ok_or_else(|| value.unexpected_type("object"))?
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), T::try_from(value)?)))
+            .collect()
+    }
+}
+
+impl<T> TryFrom<&JsonValue> for BTreeMap<String, T>
+where
+    T: for<'a> TryFrom<&'a JsonValue, Error = ParsedJsonError>,
+{
+    type Error = ParsedJsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value
+            .as_object()
+            .ok_or_else(|| value.unexpected_type("object"))?
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), T::try_from(value)?)))
+            .collect()
+    }
+}
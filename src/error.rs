@@ -1,20 +1,75 @@
+use crate::token::{Span, Token};
 use core::fmt;
 use std::error::Error;
 
 #[derive(Debug)]
 pub enum LexerError {
-    UnexpectedCharacter(char),
-    InvalidEscapeSquence(char),
-    InvalidValue(String),
-    UnexpectedEndOfLine,
+    UnexpectedCharacter(char, Span),
+    InvalidEscapeSquence(char, Span),
+    UnexpectedEndOfLine(Span),
+    LoneSurrogate(u16, Span),
+    InvalidNumber(String, Span),
+    /// Unicode whitespace other than the four characters RFC 8259 allows.
+    UnsupportedWhitespace(char, Span),
+    /// A bare word that is not exactly `true`, `false` or `null`.
+    InvalidLiteral(String, Span),
+    /// A raw U+0000 to U+001F character inside a string.
+    UnescapedControlCharacter(char, Span),
+    /// Input ended inside a string; the span starts at its opening quote.
+    UnterminatedString(Span),
 }
+
+impl LexerError {
+    /// Span of the input the error was raised at.
+    pub fn span(&self) -> Span {
+        match *self {
+            LexerError::UnexpectedCharacter(_, span)
+            | LexerError::InvalidEscapeSquence(_, span)
+            | LexerError::UnexpectedEndOfLine(span)
+            | LexerError::LoneSurrogate(_, span)
+            | LexerError::InvalidNumber(_, span)
+            | LexerError::UnsupportedWhitespace(_, span)
+            | LexerError::InvalidLiteral(_, span)
+            | LexerError::UnescapedControlCharacter(_, span)
+            | LexerError::UnterminatedString(span) => span,
+        }
+    }
+}
+
 impl fmt::Display for LexerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            LexerError::UnexpectedCharacter(ch) => write!(f, "Unexpected character: {}", ch),
-            LexerError::InvalidEscapeSquence(ch) => write!(f, "Invalid escape sequence: {}", ch),
-            LexerError::InvalidValue(ref val) => write!(f, "Invalid value: {}", val),
-            LexerError::UnexpectedEndOfLine => write!(f, "Unexpected end of line"),
+            LexerError::UnexpectedCharacter(ch, span) => {
+                write!(f, "Unexpected character: {} at {}", ch, span)
+            }
+            LexerError::InvalidEscapeSquence(ch, span) => {
+                write!(f, "Invalid escape sequence: {} at {}", ch, span)
+            }
+            LexerError::UnexpectedEndOfLine(span) => {
+                write!(f, "Unexpected end of line at {}", span)
+            }
+            LexerError::LoneSurrogate(code_unit, span) => {
+                write!(f, "Unpaired surrogate: \\u{:04X} at {}", code_unit, span)
+            }
+            LexerError::InvalidNumber(ref lexeme, span) => {
+                write!(f, "Invalid number: {} at {}", lexeme, span)
+            }
+            LexerError::UnsupportedWhitespace(ch, span) => write!(
+                f,
+                "Unsupported whitespace U+{:04X}, only space, tab, CR and LF are allowed, at {}",
+                ch as u32, span
+            ),
+            LexerError::InvalidLiteral(ref literal, span) => {
+                write!(f, "Invalid literal: {} at {}", literal, span)
+            }
+            LexerError::UnescapedControlCharacter(ch, span) => write!(
+                f,
+                "Unescaped control character U+{:04X} in string at {}",
+                ch as u32, span
+            ),
+            LexerError::UnterminatedString(span) => {
+                write!(f, "Unterminated string starting at {}", span)
+            }
         }
     }
 }
@@ -22,14 +77,84 @@ impl Error for LexerError {}
 
 #[derive(Debug)]
 pub enum ParserError {
-    UnexpectedToken(crate::token::Token),
-    UnexpectedStart(crate::token::Token),
+    UnexpectedToken(Token, Span),
+    UnexpectedStart(Token, Span),
+    UnexpectedEndOfInput(Span),
+    ExpectedKey(Token, Span),
+    /// A value was expected, the token was found instead.
+    ExpectedValue(Token, Span),
+    ExpectedNameSeperator(Token, Span),
+    /// A `,` or the closing token (first field) was expected, the second
+    /// field was found instead.
+    ExpectedSeperatorOrEnd(Token, Token, Span),
+    /// A `,` directly before the closing token, which is only accepted in
+    /// lenient mode.
+    TrailingSeperator(Token, Span),
+    TrailingCharacters(Span),
+    /// An object key seen before, with the spans of the first and the
+    /// repeated key. Only raised with `DuplicateKeys::Error`.
+    DuplicateKey(String, Span, Span),
+}
+
+impl ParserError {
+    /// Span of the token the error was raised at.
+    pub fn span(&self) -> Span {
+        match *self {
+            ParserError::UnexpectedToken(_, span)
+            | ParserError::UnexpectedStart(_, span)
+            | ParserError::UnexpectedEndOfInput(span)
+            | ParserError::ExpectedKey(_, span)
+            | ParserError::ExpectedValue(_, span)
+            | ParserError::ExpectedNameSeperator(_, span)
+            | ParserError::ExpectedSeperatorOrEnd(_, _, span)
+            | ParserError::TrailingSeperator(_, span)
+            | ParserError::TrailingCharacters(span)
+            | ParserError::DuplicateKey(_, _, span) => span,
+        }
+    }
 }
+
 impl fmt::Display for ParserError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            ParserError::UnexpectedToken(ref token) => write!(f, "Unexpected token: {:?}", token),
-            ParserError::UnexpectedStart(ref token) => write!(f, "Unexpected start: {:?}", token),
+            ParserError::UnexpectedToken(ref token, span) => {
+                write!(f, "Unexpected token: {:?} at {}", token, span)
+            }
+            ParserError::UnexpectedStart(ref token, span) => {
+                write!(f, "Unexpected start: {:?} at {}", token, span)
+            }
+            ParserError::UnexpectedEndOfInput(span) => {
+                write!(f, "Unexpected end of input at {}", span)
+            }
+            ParserError::ExpectedKey(ref token, span) => {
+                write!(f, "Expected string key, found {:?} at {}", token, span)
+            }
+            ParserError::ExpectedValue(ref token, span) => {
+                write!(f, "Expected a value, found {:?} at {}", token, span)
+            }
+            ParserError::ExpectedNameSeperator(ref token, span) => {
+                write!(f, "Expected ':', found {:?} at {}", token, span)
+            }
+            ParserError::ExpectedSeperatorOrEnd(ref end, ref token, span) => {
+                write!(
+                    f,
+                    "Expected ',' or {:?}, found {:?} at {}",
+                    end, token, span
+                )
+            }
+            ParserError::TrailingSeperator(ref end, span) => {
+                write!(f, "Trailing ',' before {:?} at {}", end, span)
+            }
+            ParserError::TrailingCharacters(span) => {
+                write!(f, "Trailing characters after the document at {}", span)
+            }
+            ParserError::DuplicateKey(ref key, first, span) => {
+                write!(
+                    f,
+                    "Duplicate key {:?} at {}, first seen at {}",
+                    key, span, first
+                )
+            }
         }
     }
 }
@@ -41,7 +166,18 @@ pub enum ParsedJsonError {
     KeyNotFound(String),
     IndexOutOfBounds(usize),
     InvalidMethodCall(String),
-    UnexpectedType,
+    /// The value is not of the type the accessor needs. Both fields are
+    /// type names such as `"string"`, see `JsonValue::type_name`.
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The number (first field) is not an integer that fits the Rust type
+    /// named in the second field.
+    NumberOutOfRange(String, &'static str),
+    /// An array with the wrong number of elements (second field) where a
+    /// fixed number (first field) is needed.
+    LengthMismatch(usize, usize),
 }
 impl std::fmt::Display for ParsedJsonError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -55,18 +191,124 @@ impl std::fmt::Display for ParsedJsonError {
             ParsedJsonError::InvalidMethodCall(err) => {
                 write!(f, "{}", err)
             }
-            ParsedJsonError::UnexpectedType => write!(f, "Unexpected type in JSON value"),
+            ParsedJsonError::UnexpectedType { expected, found } => {
+                write!(f, "Expected {}, found {}", expected, found)
+            }
+            ParsedJsonError::NumberOutOfRange(number, target) => {
+                write!(f, "Number {} does not fit in {}", number, target)
+            }
+            ParsedJsonError::LengthMismatch(expected, found) => {
+                write!(f, "Expected {} elements, found {}", expected, found)
+            }
         }
     }
 }
 
 impl std::error::Error for ParsedJsonError {}
 
+#[derive(Debug)]
+pub enum SerializerError {
+    /// NaN and the infinities have no JSON representation.
+    NonFiniteNumber(f64),
+    /// An object repeats this key. RFC 8785 canonical output needs unique
+    /// member names, since implementations disagree on which value counts.
+    DuplicateKey(String),
+    Io(std::io::Error),
+}
+impl fmt::Display for SerializerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SerializerError::NonFiniteNumber(number) => {
+                write!(f, "Cannot serialize non-finite number: {}", number)
+            }
+            SerializerError::DuplicateKey(ref key) => {
+                write!(f, "Cannot canonicalize duplicate key: {:?}", key)
+            }
+            SerializerError::Io(ref err) => write!(f, "Write failed: {}", err),
+        }
+    }
+}
+
+impl Error for SerializerError {}
+
+impl From<std::io::Error> for SerializerError {
+    fn from(err: std::io::Error) -> SerializerError {
+        SerializerError::Io(err)
+    }
+}
+
+/// One step from a value to a nested one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// A `FromJson` failure together with the path to the value that caused
+/// it, displayed like `.users[3].email: Expected string, found number`.
+#[derive(Debug)]
+pub struct FromJsonError {
+    // innermost segment first, since segments are added while unwinding
+    reversed_path: Vec<PathSegment>,
+    error: ParsedJsonError,
+}
+
+impl FromJsonError {
+    /// Marks the error as raised inside the member `key`.
+    pub fn at_key(mut self, key: &str) -> Self {
+        self.reversed_path.push(PathSegment::Key(key.to_string()));
+        self
+    }
+
+    /// Marks the error as raised inside the element at `index`.
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.reversed_path.push(PathSegment::Index(index));
+        self
+    }
+
+    /// Path from the root to the failing value, outermost segment first.
+    pub fn path(&self) -> impl Iterator<Item = &PathSegment> {
+        self.reversed_path.iter().rev()
+    }
+
+    pub fn error(&self) -> &ParsedJsonError {
+        &self.error
+    }
+}
+
+impl From<ParsedJsonError> for FromJsonError {
+    fn from(error: ParsedJsonError) -> FromJsonError {
+        FromJsonError {
+            reversed_path: Vec::new(),
+            error,
+        }
+    }
+}
+
+impl fmt::Display for FromJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.reversed_path.is_empty() {
+            return write!(f, "{}", self.error);
+        }
+        for segment in self.path() {
+            match *segment {
+                PathSegment::Key(ref key) => write!(f, ".{}", key)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
+impl Error for FromJsonError {}
+
 #[derive(Debug)]
 pub enum JsonError {
     LexerError(LexerError),
     ParserError(ParserError),
     ParsedJsonError(ParsedJsonError),
+    SerializerError(SerializerError),
+    FromJsonError(FromJsonError),
 }
 
 impl fmt::Display for JsonError {
@@ -75,6 +317,8 @@ impl fmt::Display for JsonError {
             JsonError::LexerError(ref err) => write!(f, "Lexer Error: {:?}", err),
             JsonError::ParserError(ref err) => write!(f, "Parser Error: {:?}", err),
             JsonError::ParsedJsonError(ref err) => write!(f, "Json Error: {:?}", err),
+            JsonError::SerializerError(ref err) => write!(f, "Serializer Error: {:?}", err),
+            JsonError::FromJsonError(ref err) => write!(f, "Conversion Error: {}", err),
         }
     }
 }
@@ -97,3 +341,15 @@ impl From<ParserError> for JsonError {
         JsonError::ParserError(err)
     }
 }
+
+impl From<SerializerError> for JsonError {
+    fn from(err: SerializerError) -> JsonError {
+        JsonError::SerializerError(err)
+    }
+}
+
+impl From<FromJsonError> for JsonError {
+    fn from(err: FromJsonError) -> JsonError {
+        JsonError::FromJsonError(err)
+    }
+}
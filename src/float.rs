@@ -0,0 +1,321 @@
+use std::cmp::Ordering;
+
+/// Minimal unsigned big integer, little-endian base 2^32, just large enough
+/// for the exact arithmetic of shortest float formatting.
+#[derive(Clone, PartialEq, Eq)]
+struct Big {
+    limbs: Vec<u32>,
+}
+
+impl Big {
+    fn from_u64(value: u64) -> Big {
+        let mut big = Big {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        };
+        big.trim();
+        big
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0u64;
+        for limb in self.limbs.iter_mut() {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+
+    fn mul_pow10(&mut self, exponent: u32) {
+        for _ in 0..exponent {
+            self.mul_small(10);
+        }
+    }
+
+    fn shl(&mut self, bits: u32) {
+        let words = (bits / 32) as usize;
+        let bits = bits % 32;
+        if bits > 0 {
+            let mut carry = 0u32;
+            for limb in self.limbs.iter_mut() {
+                let shifted = (*limb << bits) | carry;
+                carry = *limb >> (32 - bits);
+                *limb = shifted;
+            }
+            if carry > 0 {
+                self.limbs.push(carry);
+            }
+        }
+        if !self.limbs.is_empty() {
+            self.limbs.splice(0..0, std::iter::repeat_n(0, words));
+        }
+    }
+
+    fn add(&self, other: &Big) -> Big {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+        for index in 0..self.limbs.len().max(other.limbs.len()) {
+            let a = *self.limbs.get(index).unwrap_or(&0) as u64;
+            let b = *other.limbs.get(index).unwrap_or(&0) as u64;
+            let sum = a + b + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+        Big { limbs }
+    }
+
+    /// `self -= other`, where `other <= self`.
+    fn sub_assign(&mut self, other: &Big) {
+        let mut borrow = 0i64;
+        for index in 0..self.limbs.len() {
+            let b = *other.limbs.get(index).unwrap_or(&0) as i64;
+            let mut difference = self.limbs[index] as i64 - b - borrow;
+            borrow = 0;
+            if difference < 0 {
+                difference += 1 << 32;
+                borrow = 1;
+            }
+            self.limbs[index] = difference as u32;
+        }
+        self.trim();
+    }
+}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Big) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Big) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+/// Shortest decimal digits that read back as `value`, which must be finite
+/// and positive, together with the exponent `k` such that
+/// `value = 0.d1d2... * 10^k`. When two shortest candidates are equally
+/// close the even one wins, as ECMAScript requires.
+///
+/// This is the free-format algorithm of Burger and Dybvig ("Printing
+/// Floating-Point Numbers Quickly and Accurately", 1996) on exact integers.
+fn shortest_digits(value: f64) -> (String, i32) {
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    // a value exactly halfway to a neighbour reads back as the even mantissa
+    let inclusive = mantissa % 2 == 0;
+    // the gap below is half as wide right above a power of two
+    let uneven_gap = fraction == 0 && biased_exponent > 1;
+
+    // value = r / s, the rounding interval is (r - m_minus, r + m_plus) / s
+    let mut r = Big::from_u64(mantissa);
+    let mut s = Big::from_u64(1);
+    let mut m_plus = Big::from_u64(1);
+    let mut m_minus = Big::from_u64(1);
+    if exponent >= 0 {
+        r.shl(exponent as u32);
+        m_plus.shl(exponent as u32);
+        m_minus.shl(exponent as u32);
+    } else {
+        s.shl((-exponent) as u32);
+    }
+    let scale = if uneven_gap { 2 } else { 1 };
+    r.shl(scale);
+    s.shl(scale);
+    m_plus.shl(scale - 1);
+
+    let mut k = (value.log10() - 1e-10).ceil() as i32;
+    if k >= 0 {
+        s.mul_pow10(k as u32);
+    } else {
+        r.mul_pow10((-k) as u32);
+        m_plus.mul_pow10((-k) as u32);
+        m_minus.mul_pow10((-k) as u32);
+    }
+    let high = r.add(&m_plus);
+    if high > s || (inclusive && high == s) {
+        s.mul_small(10);
+        k += 1;
+    }
+
+    let mut digits = String::new();
+    loop {
+        r.mul_small(10);
+        m_plus.mul_small(10);
+        m_minus.mul_small(10);
+        let mut digit = 0u8;
+        while r >= s {
+            r.sub_assign(&s);
+            digit += 1;
+        }
+        let low_end = r < m_minus || (inclusive && r == m_minus);
+        let high = r.add(&m_plus);
+        let high_end = high > s || (inclusive && high == s);
+        if !low_end && !high_end {
+            digits.push((b'0' + digit) as char);
+            continue;
+        }
+        let round_up = if low_end && high_end {
+            let mut twice = r.clone();
+            twice.mul_small(2);
+            match twice.cmp(&s) {
+                Ordering::Less => false,
+                Ordering::Greater => true,
+                Ordering::Equal => digit % 2 == 1,
+            }
+        } else {
+            high_end
+        };
+        digits.push((b'0' + digit + u8::from(round_up)) as char);
+        return (digits, k);
+    }
+}
+
+/// Appends the shortest text that reads back as the finite `value`, laid out
+/// like ECMAScript's `Number.prototype.toString`: integral values have no
+/// `.0`, magnitudes from 1e21 up or below 1e-6 use exponent form such as
+/// `1e+21` or `1.5e-7`. Negative zero keeps its sign.
+pub(crate) fn write_shortest(out: &mut String, value: f64) {
+    if value.is_sign_negative() {
+        out.push('-');
+    }
+    if value == 0.0 {
+        out.push('0');
+        return;
+    }
+    let (digits, n) = shortest_digits(value.abs());
+    let k = digits.len() as i32;
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', (-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n - 1 < 0 { '-' } else { '+' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+}
+
+/// Powers of ten that are exact as `f64`.
+const EXACT_POWERS_OF_TEN: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+/// Largest integer below which every integer is exact as `f64`.
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+
+/// Converts a lexeme that already matched the JSON number grammar to the
+/// nearest `f64`, reading the digits in place.
+///
+/// Integers and decimals whose digits and power of ten are both exact as
+/// `f64` are converted with a single correctly rounded multiply or divide
+/// (Clinger's fast path). Everything else goes to the standard library's
+/// Eisel-Lemire implementation on the same borrowed slice, so results are
+/// always bit-exact.
+pub(crate) fn parse_decimal(lexeme: &str) -> f64 {
+    let bytes = lexeme.as_bytes();
+    let negative = bytes.first() == Some(&b'-');
+    let mut index = usize::from(negative);
+    let mut mantissa = 0u64;
+    let mut mantissa_digits = 0;
+    let mut exponent = 0i64;
+    let mut truncated = false;
+    let mut in_fraction = false;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match byte {
+            b'0'..=b'9' => {
+                let digit = (byte - b'0') as u64;
+                if mantissa_digits < 19 {
+                    mantissa = mantissa * 10 + digit;
+                    if mantissa > 0 {
+                        mantissa_digits += 1;
+                    }
+                    if in_fraction {
+                        exponent -= 1;
+                    }
+                } else {
+                    truncated |= digit != 0;
+                    if !in_fraction {
+                        exponent += 1;
+                    }
+                }
+            }
+            b'.' => in_fraction = true,
+            _ => break,
+        }
+        index += 1;
+    }
+    if index < bytes.len() {
+        // exponent part, `e` or `E` with an optional sign
+        index += 1;
+        let negative_exponent = bytes[index] == b'-';
+        if matches!(bytes[index], b'+' | b'-') {
+            index += 1;
+        }
+        let mut explicit = 0i64;
+        for &byte in &bytes[index..] {
+            explicit = (explicit * 10 + (byte - b'0') as i64).min(100_000);
+        }
+        exponent += if negative_exponent {
+            -explicit
+        } else {
+            explicit
+        };
+    }
+
+    if !truncated && mantissa <= MAX_EXACT_INTEGER {
+        let value = mantissa as f64;
+        let fast = match exponent {
+            0 => Some(value),
+            1..=22 => Some(value * EXACT_POWERS_OF_TEN[exponent as usize]),
+            -22..=-1 => Some(value / EXACT_POWERS_OF_TEN[(-exponent) as usize]),
+            // shift surplus powers of ten into the digits while they stay exact
+            23..=37 => 10u64
+                .checked_pow((exponent - 22) as u32)
+                .and_then(|scale| mantissa.checked_mul(scale))
+                .filter(|shifted| *shifted <= MAX_EXACT_INTEGER)
+                .map(|shifted| shifted as f64 * EXACT_POWERS_OF_TEN[22]),
+            _ if mantissa == 0 => Some(0.0),
+            _ => None,
+        };
+        if let Some(value) = fast {
+            return if negative { -value } else { value };
+        }
+    }
+    lexeme.parse().unwrap_or(f64::NAN)
+}
@@ -1,27 +1,90 @@
-use crate::{error::LexerError, token::Token};
+use crate::{
+    error::LexerError,
+    number::JsonNumber,
+    token::{Span, SpannedToken, Token},
+};
 use std::{iter, str::Chars};
 
+/// Switches that relax how strictly the lexer follows RFC 8259.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LexerOptions {
+    /// Replace lone or mismatched UTF-16 surrogates in `\uXXXX` escapes with
+    /// U+FFFD instead of failing with `LexerError::LoneSurrogate`.
+    pub lossy_surrogates: bool,
+    /// Keep the decimal text of every number so it round-trips exactly,
+    /// see `JsonNumber::as_str_lossless`.
+    pub lossless_numbers: bool,
+    /// Skip a UTF-8 byte order mark at the very start of the input.
+    pub skip_bom: bool,
+    /// Accept raw control characters (U+0000 to U+001F) inside strings
+    /// instead of failing with `LexerError::UnescapedControlCharacter`.
+    pub allow_control_characters: bool,
+}
+
 #[derive(Clone)]
 pub struct Lexer<'a> {
+    data: &'a str,
     token_iter: iter::Peekable<Chars<'a>>,
+    options: LexerOptions,
+    offset: usize,
+    line: usize,
+    column: usize,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(data: &'a str) -> Self {
+        Lexer::with_options(data, LexerOptions::default())
+    }
+
+    pub fn with_options(data: &'a str, options: LexerOptions) -> Self {
         Lexer {
+            data,
             token_iter: data.chars().peekable(),
+            options,
+            offset: 0,
+            line: 1,
+            column: 1,
         }
     }
 
     fn advance(&mut self) -> Option<char> {
-        self.token_iter.next()
+        let ch = self.token_iter.next()?;
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    /// Empty span at the current position, used as the start of a token.
+    fn mark(&self) -> Span {
+        Span::new(self.offset, self.offset, self.line, self.column)
     }
 
-    fn lex_string(&mut self) -> Result<String, LexerError> {
+    /// Empty span where lexing stopped. After a successful `lex` this is the
+    /// end of the input, past any trailing whitespace.
+    pub fn end(&self) -> Span {
+        self.mark()
+    }
+
+    /// Extends `start` up to the current position.
+    fn span_from(&self, start: Span) -> Span {
+        Span {
+            end: self.offset,
+            ..start
+        }
+    }
+
+    /// Lexes the rest of a string whose opening quote sits at `quote`.
+    fn lex_string(&mut self, quote: Span) -> Result<String, LexerError> {
         let mut string_value = String::new();
         while let Some(value) = self.token_iter.peek() {
             match *value {
                 '\\' => {
+                    let start = self.mark();
                     self.advance();
                     match self.advance() {
                         Some('n') => string_value.push('\n'),
@@ -31,12 +94,27 @@ impl<'a> Lexer<'a> {
                         Some('"') => {
                             string_value.push('\"');
                         }
-                        ch => return Err(LexerError::InvalidEscapeSquence(ch.unwrap_or(' '))),
+                        Some('/') => string_value.push('/'),
+                        Some('b') => string_value.push('\u{8}'),
+                        Some('f') => string_value.push('\u{c}'),
+                        Some('u') => self.lex_unicode_escape(quote, start, &mut string_value)?,
+                        Some(ch) => {
+                            return Err(LexerError::InvalidEscapeSquence(ch, self.span_from(start)))
+                        }
+                        None => return Err(LexerError::UnterminatedString(self.span_from(quote))),
                     }
                 }
                 '"' => {
                     self.advance();
-                    break;
+                    return Ok(string_value);
+                }
+                val if val < '\u{20}' && !self.options.allow_control_characters => {
+                    let start = self.mark();
+                    self.advance();
+                    return Err(LexerError::UnescapedControlCharacter(
+                        val,
+                        self.span_from(start),
+                    ));
                 }
                 val => {
                     string_value.push(val);
@@ -44,144 +122,234 @@ impl<'a> Lexer<'a> {
                 }
             }
         }
-        Ok(string_value)
+        Err(LexerError::UnterminatedString(self.span_from(quote)))
     }
 
-    fn lex_number(&mut self) -> Result<String, LexerError> {
-        let mut number_value = String::new();
-        while let Some(value) = self.token_iter.peek() {
-            match *value {
-                '0'..='9' | '-' | '.' | 'e' | 'E' => {
-                    number_value.push(*value);
-                    self.advance();
-                }
-                _ => return Ok(number_value),
+    /// Reads the four hex digits of a `\uXXXX` escape.
+    fn lex_hex4(&mut self, quote: Span, start: Span) -> Result<u16, LexerError> {
+        let mut code_unit = 0u16;
+        for _ in 0..4 {
+            match self.advance() {
+                Some(ch) => match ch.to_digit(16) {
+                    Some(digit) => code_unit = code_unit * 16 + digit as u16,
+                    None => {
+                        return Err(LexerError::InvalidEscapeSquence(ch, self.span_from(start)))
+                    }
+                },
+                None => return Err(LexerError::UnterminatedString(self.span_from(quote))),
             }
         }
-        Ok(number_value)
+        Ok(code_unit)
     }
 
-    fn lex_boolean(&mut self) -> Result<bool, LexerError> {
-        let mut boolean_value = String::new();
-        while let Some(value) = self.token_iter.peek() {
-            match *value {
-                't' => {
-                    let mut counter = 1;
-                    while counter <= 4 {
-                        match self.advance() {
-                            Some(value) => {
-                                boolean_value.push(value);
-                                counter += 1;
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
+    /// True when the next two characters start another `\uXXXX` escape.
+    fn next_is_unicode_escape(&self) -> bool {
+        let mut lookahead = self.token_iter.clone();
+        lookahead.next() == Some('\\') && lookahead.next() == Some('u')
+    }
+
+    fn lone_surrogate(
+        &self,
+        code_unit: u16,
+        span: Span,
+        string_value: &mut String,
+    ) -> Result<(), LexerError> {
+        if self.options.lossy_surrogates {
+            string_value.push(char::REPLACEMENT_CHARACTER);
+            Ok(())
+        } else {
+            Err(LexerError::LoneSurrogate(code_unit, span))
+        }
+    }
+
+    /// Decodes a `\uXXXX` escape whose backslash sits at `start`, joining a
+    /// high surrogate with the low surrogate escape that follows it.
+    fn lex_unicode_escape(
+        &mut self,
+        quote: Span,
+        mut start: Span,
+        string_value: &mut String,
+    ) -> Result<(), LexerError> {
+        let mut code_unit = self.lex_hex4(quote, start)?;
+        loop {
+            let span = self.span_from(start);
+            match code_unit {
+                0xD800..=0xDBFF => {
+                    if !self.next_is_unicode_escape() {
+                        return self.lone_surrogate(code_unit, span, string_value);
                     }
-                }
-                'f' => {
-                    let mut counter = 1;
-                    while counter <= 5 {
-                        match self.advance() {
-                            Some(value) => {
-                                boolean_value.push(value);
-                                counter += 1;
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
+                    let low_start = self.mark();
+                    self.advance();
+                    self.advance();
+                    let low = self.lex_hex4(quote, low_start)?;
+                    if let 0xDC00..=0xDFFF = low {
+                        let high_bits = (code_unit as u32 - 0xD800) << 10;
+                        let low_bits = low as u32 - 0xDC00;
+                        let scalar = char::from_u32(0x10000 + high_bits + low_bits)
+                            .unwrap_or(char::REPLACEMENT_CHARACTER);
+                        string_value.push(scalar);
+                        return Ok(());
                     }
+                    self.lone_surrogate(code_unit, span, string_value)?;
+                    code_unit = low;
+                    start = low_start;
+                }
+                0xDC00..=0xDFFF => return self.lone_surrogate(code_unit, span, string_value),
+                _ => {
+                    let scalar =
+                        char::from_u32(code_unit as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+                    string_value.push(scalar);
+                    return Ok(());
                 }
-                ch => return Err(LexerError::UnexpectedCharacter(ch)),
             }
-            if boolean_value.as_str().contains("false") {
-                return Ok(false);
-            } else if boolean_value.as_str().contains("true") {
-                return Ok(true);
-            } else {
-                return Err(LexerError::InvalidValue(boolean_value));
+        }
+    }
+
+    /// Consumes the next character if `accept` matches it.
+    fn skip_if(&mut self, accept: impl Fn(char) -> bool) -> bool {
+        match self.token_iter.peek() {
+            Some(&ch) if accept(ch) => {
+                self.advance();
+                true
             }
+            _ => false,
         }
-        return Err(LexerError::InvalidValue(boolean_value));
     }
 
-    fn lex_null(&mut self) -> Result<Token, LexerError> {
-        let mut null_value = String::new();
-        let mut counter = 1;
-        while counter <= 4 {
-            match self.advance() {
-                Some(value) => {
-                    null_value.push(value);
-                    counter += 1;
-                }
-                _ => {
-                    break;
-                }
+    fn skip_digits(&mut self) -> bool {
+        let mut any = false;
+        while self.skip_if(|ch| ch.is_ascii_digit()) {
+            any = true;
+        }
+        any
+    }
+
+    /// Swallows the rest of a malformed number so the error carries the whole
+    /// lexeme, e.g. `1-2` rather than just `1`.
+    fn invalid_number(&mut self, start: Span) -> LexerError {
+        while self.skip_if(|ch| matches!(ch, '0'..='9' | '-' | '+' | '.' | 'e' | 'E')) {}
+        let number_value = self.data[start.start..self.offset].to_string();
+        LexerError::InvalidNumber(number_value, self.span_from(start))
+    }
+
+    /// Lexes a number following the RFC 8259 grammar:
+    /// `[ minus ] int [ frac ] [ exp ]`. The lexeme is borrowed from the
+    /// input rather than copied.
+    fn lex_number(&mut self, start: Span) -> Result<&'a str, LexerError> {
+        self.skip_if(|ch| ch == '-');
+        if self.skip_if(|ch| ch == '0') {
+            if matches!(self.token_iter.peek(), Some('0'..='9')) {
+                return Err(self.invalid_number(start));
             }
+        } else if !self.skip_digits() {
+            return Err(self.invalid_number(start));
         }
-        if null_value.contains("null") {
-            return Ok(Token::ValueNil);
-        } else {
-            return Err(LexerError::InvalidValue(null_value));
+        if self.skip_if(|ch| ch == '.') && !self.skip_digits() {
+            return Err(self.invalid_number(start));
+        }
+        if self.skip_if(|ch| ch == 'e' || ch == 'E') {
+            self.skip_if(|ch| ch == '+' || ch == '-');
+            if !self.skip_digits() {
+                return Err(self.invalid_number(start));
+            }
+        }
+        if matches!(
+            self.token_iter.peek(),
+            Some('0'..='9' | '-' | '+' | '.' | 'e' | 'E')
+        ) {
+            return Err(self.invalid_number(start));
+        }
+        Ok(&self.data[start.start..self.offset])
+    }
+
+    /// Lexes `true`, `false` or `null`. The whole run of characters up to the
+    /// next delimiter must match, so `trueX` or `tru,` are rejected.
+    fn lex_literal(&mut self, start: Span) -> Result<Token, LexerError> {
+        let mut literal_value = String::new();
+        while let Some(&value) = self.token_iter.peek() {
+            if value.is_whitespace() || matches!(value, '{' | '}' | '[' | ']' | ':' | ',' | '"') {
+                break;
+            }
+            literal_value.push(value);
+            self.advance();
+        }
+        match literal_value.as_str() {
+            "true" => Ok(Token::ValueBoolean(true)),
+            "false" => Ok(Token::ValueBoolean(false)),
+            "null" => Ok(Token::ValueNil),
+            _ => Err(LexerError::InvalidLiteral(
+                literal_value,
+                self.span_from(start),
+            )),
         }
     }
 
-    pub fn lex(&mut self) -> Result<Vec<Token>, LexerError> {
+    pub fn lex(&mut self) -> Result<Vec<SpannedToken>, LexerError> {
         let mut tokens = Vec::new();
-        while let Some(ch) = self.token_iter.peek() {
-            match ch {
+        if self.options.skip_bom && self.offset == 0 && self.token_iter.peek() == Some(&'\u{feff}')
+        {
+            self.advance();
+            // the mark is invisible, columns start after it
+            self.column = 1;
+        }
+        while let Some(&ch) = self.token_iter.peek() {
+            let start = self.mark();
+            let token = match ch {
                 '{' => {
-                    tokens.push(Token::BeginObject);
                     self.advance();
+                    Token::BeginObject
                 }
                 '}' => {
-                    tokens.push(Token::EndObject);
                     self.advance();
+                    Token::EndObject
                 }
                 '[' => {
-                    tokens.push(Token::BeginArray);
                     self.advance();
+                    Token::BeginArray
                 }
                 ']' => {
-                    tokens.push(Token::EndArray);
                     self.advance();
+                    Token::EndArray
                 }
                 ':' => {
-                    tokens.push(Token::NameSeperator);
                     self.advance();
+                    Token::NameSeperator
                 }
                 ',' => {
-                    tokens.push(Token::ValueSeperator);
                     self.advance();
+                    Token::ValueSeperator
                 }
                 '"' => {
                     self.advance();
-                    let string_value = self.lex_string()?;
-                    tokens.push(Token::ValueString(string_value));
+                    let string_value = self.lex_string(start)?;
+                    Token::ValueString(string_value)
                 }
                 '0'..='9' | '-' => {
-                    let number_value = self.lex_number()?;
-                    tokens.push(Token::ValueNumber(number_value.parse().unwrap()));
-                }
-                't' | 'f' => {
-                    let boolean_value = self.lex_boolean()?;
-                    tokens.push(Token::ValueBoolean(boolean_value));
-                }
-                'n' => {
-                    let null_value = self.lex_null()?;
-                    tokens.push(null_value);
+                    let number_value = self.lex_number(start)?;
+                    if self.options.lossless_numbers {
+                        Token::ValueNumber(JsonNumber::from_lexeme(number_value.to_string()))
+                    } else {
+                        Token::ValueNumber(JsonNumber::parse_lexeme(number_value))
+                    }
                 }
-                ' ' => {
+                'a'..='z' | 'A'..='Z' => self.lex_literal(start)?,
+                ' ' | '\t' | '\n' | '\r' => {
                     self.advance();
+                    continue;
                 }
-                '\n' => {
+                val if val.is_whitespace() => {
                     self.advance();
+                    return Err(LexerError::UnsupportedWhitespace(
+                        val,
+                        self.span_from(start),
+                    ));
                 }
                 val => {
-                    return Err(LexerError::UnexpectedCharacter(*val));
+                    self.advance();
+                    return Err(LexerError::UnexpectedCharacter(val, self.span_from(start)));
                 }
-            }
+            };
+            tokens.push(SpannedToken::new(token, self.span_from(start)));
         }
         Ok(tokens)
     }
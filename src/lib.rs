@@ -1,7 +1,18 @@
+#[macro_use]
+mod macros;
+
+pub mod canonical;
+mod convert;
 pub mod error;
+mod float;
 pub mod lexer;
+pub mod number;
+pub mod object;
 pub mod parser;
+pub mod pretty;
+pub mod serializer;
 pub mod token;
+pub mod typed;
 
 #[cfg(test)]
 mod tests;
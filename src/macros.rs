@@ -0,0 +1,87 @@
+/// Builds a `JsonValue` from JSON-like syntax. Values and keys can be any
+/// Rust expression that converts into a `JsonValue` (or a `String` for keys),
+/// and trailing commas are allowed:
+///
+/// ```
+/// use jsruston::json;
+///
+/// let id = 7;
+/// let key = "tags";
+/// let value = json!({
+///     "id": id,
+///     key: ["a", id * 2, [], {}],
+///     "meta": { "draft": true, "owner": null, },
+/// });
+/// assert_eq!(
+///     value.to_string(),
+///     r#"{"id":7,"tags":["a",14,[],{}],"meta":{"draft":true,"owner":null}}"#
+/// );
+/// ```
+///
+/// Every token is consumed by one macro step, so very long literals may need
+/// a higher `#![recursion_limit]`.
+#[macro_export]
+macro_rules! json {
+    (null) => {
+        $crate::parser::JsonValue::Null
+    };
+    ([]) => {
+        $crate::parser::JsonValue::Array(::std::vec::Vec::new())
+    };
+    ([ $($tt:tt)+ ]) => {
+        $crate::parser::JsonValue::Array($crate::json_internal!(@array [] () $($tt)+))
+    };
+    ({}) => {
+        $crate::parser::JsonValue::Object($crate::object::JsonObject::new())
+    };
+    ({ $($tt:tt)+ }) => {{
+        let mut object = $crate::object::JsonObject::new();
+        $crate::json_internal!(@object object [] $($tt)+);
+        $crate::parser::JsonValue::Object(object)
+    }};
+    ($other:expr) => {
+        $crate::parser::JsonValue::from($other)
+    };
+}
+
+/// Token muncher behind `json!`. Elements and members are collected token by
+/// token up to the next top-level `,`, so values may be any expression.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! json_internal {
+    // arrays: `[...]` holds the finished elements, `(...)` the tokens of
+    // the element read so far
+    (@array [$($elements:expr,)*] ()) => {
+        ::std::vec![$($elements,)*]
+    };
+    (@array [$($elements:expr,)*] ($($element:tt)+)) => {
+        ::std::vec![$($elements,)* $crate::json!($($element)+)]
+    };
+    (@array [$($elements:expr,)*] ($($element:tt)+) , $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elements,)* $crate::json!($($element)+),] () $($rest)*)
+    };
+    (@array [$($elements:expr,)*] ($($element:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elements,)*] ($($element)* $next) $($rest)*)
+    };
+
+    // objects: `[...]` holds the key read so far, up to its `:`
+    (@object $object:ident []) => {};
+    (@object $object:ident [$($key:tt)+] : $($rest:tt)*) => {
+        $crate::json_internal!(@member $object [$($key)+] () $($rest)*);
+    };
+    (@object $object:ident [$($key:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::json_internal!(@object $object [$($key)* $next] $($rest)*);
+    };
+
+    // members: `(...)` holds the tokens of the value read so far
+    (@member $object:ident [$($key:tt)+] ($($value:tt)+)) => {
+        $object.insert($($key)+, $crate::json!($($value)+));
+    };
+    (@member $object:ident [$($key:tt)+] ($($value:tt)+) , $($rest:tt)*) => {
+        $object.insert($($key)+, $crate::json!($($value)+));
+        $crate::json_internal!(@object $object [] $($rest)*);
+    };
+    (@member $object:ident [$($key:tt)+] ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::json_internal!(@member $object [$($key)+] ($($value)* $next) $($rest)*);
+    };
+}
@@ -10,6 +10,6 @@ fn main() -> Result<(), Box<dyn Error>> {
     }"#;
     let parserd_json = Parser::parse_json(john)?;
     let key = &parserd_json["key-o"];
-    println!("parsed json value: {:?}", key);
+    println!("parsed json value: {}", key);
     Ok(())
 }
@@ -0,0 +1,231 @@
+use crate::float::{parse_decimal, write_shortest};
+use std::{cmp::Ordering, fmt};
+
+#[derive(Clone)]
+enum Repr {
+    PosInt(u64),
+    NegInt(i64),
+    Float(f64),
+    Lossless(String),
+}
+
+/// A JSON number that keeps integers exact instead of forcing them through
+/// `f64`. Non-negative integers are stored as `u64`, negative ones as `i64`,
+/// everything else as `f64`. With `LexerOptions::lossless_numbers` the lexer
+/// keeps the original decimal text instead, for arbitrary precision.
+///
+/// Numbers compare by value, whatever their representation: `2`, `2.0`
+/// and a lossless `2.00` are all equal.
+#[derive(Clone)]
+pub struct JsonNumber {
+    repr: Repr,
+}
+
+impl JsonNumber {
+    /// Builds a number from a lexeme that already matched the RFC 8259 number
+    /// grammar. Integers that overflow 64 bits fall back to `f64`, and
+    /// numbers too large for `f64` keep their text so they can be written back.
+    pub(crate) fn parse_lexeme(lexeme: &str) -> JsonNumber {
+        let is_integer = !lexeme.contains(['.', 'e', 'E']);
+        if is_integer && lexeme != "-0" {
+            if let Ok(value) = lexeme.parse::<u64>() {
+                return JsonNumber::from(value);
+            }
+            if let Ok(value) = lexeme.parse::<i64>() {
+                return JsonNumber::from(value);
+            }
+        }
+        let value = parse_decimal(lexeme);
+        if value.is_infinite() {
+            return JsonNumber::from_lexeme(lexeme.to_string());
+        }
+        JsonNumber::from(value)
+    }
+
+    /// Keeps `lexeme` verbatim. The caller guarantees it is a valid number.
+    pub(crate) fn from_lexeme(lexeme: String) -> JsonNumber {
+        JsonNumber {
+            repr: Repr::Lossless(lexeme),
+        }
+    }
+
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    pub fn is_f64(&self) -> bool {
+        match self.repr {
+            Repr::Float(_) => true,
+            Repr::Lossless(ref lexeme) => lexeme.contains(['.', 'e', 'E']),
+            _ => false,
+        }
+    }
+
+    /// False for NaN and the infinities, which JSON cannot represent.
+    pub fn is_finite(&self) -> bool {
+        match self.repr {
+            Repr::Float(value) => value.is_finite(),
+            _ => true,
+        }
+    }
+
+    /// The value as `i64` if it is an integer that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.repr {
+            Repr::PosInt(value) => i64::try_from(value).ok(),
+            Repr::NegInt(value) => Some(value),
+            Repr::Float(_) => None,
+            Repr::Lossless(ref lexeme) => lexeme.parse().ok(),
+        }
+    }
+
+    /// The value as `u64` if it is a non-negative integer that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.repr {
+            Repr::PosInt(value) => Some(value),
+            Repr::NegInt(_) | Repr::Float(_) => None,
+            Repr::Lossless(ref lexeme) => lexeme.parse().ok(),
+        }
+    }
+
+    /// The value as `f64`, rounding integers and lexemes that need more
+    /// precision.
+    pub fn as_f64(&self) -> f64 {
+        match self.repr {
+            Repr::PosInt(value) => value as f64,
+            Repr::NegInt(value) => value as f64,
+            Repr::Float(value) => value,
+            Repr::Lossless(ref lexeme) => lexeme.parse().unwrap_or(f64::NAN),
+        }
+    }
+
+    /// The original decimal text, if the number was lexed losslessly or is
+    /// too large for `f64`.
+    pub fn as_str_lossless(&self) -> Option<&str> {
+        match self.repr {
+            Repr::Lossless(ref lexeme) => Some(lexeme),
+            _ => None,
+        }
+    }
+}
+
+impl From<u64> for JsonNumber {
+    fn from(value: u64) -> Self {
+        JsonNumber {
+            repr: Repr::PosInt(value),
+        }
+    }
+}
+
+impl From<i64> for JsonNumber {
+    fn from(value: i64) -> Self {
+        let repr = match u64::try_from(value) {
+            Ok(value) => Repr::PosInt(value),
+            Err(_) => Repr::NegInt(value),
+        };
+        JsonNumber { repr }
+    }
+}
+
+impl From<f64> for JsonNumber {
+    fn from(value: f64) -> Self {
+        JsonNumber {
+            repr: Repr::Float(value),
+        }
+    }
+}
+
+/// Magnitude below which every integral `f64` converts to `i128` exactly.
+const I128_RANGE: f64 = 1.7e38;
+
+/// A number reduced to a form in which equal values look the same.
+enum Value<'a> {
+    Integer(i128),
+    /// Not integral, or integral but beyond `i128`.
+    Float(f64),
+    /// A lossless lexeme too large for `f64`, compared by its text.
+    Overflow(&'a str, f64),
+}
+
+impl JsonNumber {
+    fn value(&self) -> Value<'_> {
+        let float = match self.repr {
+            Repr::PosInt(value) => return Value::Integer(value as i128),
+            Repr::NegInt(value) => return Value::Integer(value as i128),
+            Repr::Float(value) => value,
+            Repr::Lossless(ref lexeme) => {
+                if let Ok(value) = lexeme.parse::<i128>() {
+                    return Value::Integer(value);
+                }
+                let value = parse_decimal(lexeme);
+                if value.is_infinite() {
+                    return Value::Overflow(lexeme, value);
+                }
+                value
+            }
+        };
+        if float.fract() == 0.0 && float.abs() < I128_RANGE {
+            Value::Integer(float as i128)
+        } else {
+            Value::Float(float)
+        }
+    }
+}
+
+impl PartialEq for JsonNumber {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.value(), other.value()) {
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::Overflow(a, _), Value::Overflow(b, _)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl PartialOrd for JsonNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        let as_f64 = |value: Value| match value {
+            Value::Integer(value) => value as f64,
+            Value::Float(value) | Value::Overflow(_, value) => value,
+        };
+        match (self.value(), other.value()) {
+            (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(&b),
+            (a, b) => match as_f64(a).partial_cmp(&as_f64(b)) {
+                // distinct values that round to the same f64
+                Some(Ordering::Equal) => None,
+                ordering => ordering,
+            },
+        }
+    }
+}
+
+impl fmt::Display for JsonNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::PosInt(value) => write!(f, "{}", value),
+            Repr::NegInt(value) => write!(f, "{}", value),
+            Repr::Float(value) if value.is_finite() => {
+                let mut out = String::new();
+                write_shortest(&mut out, value);
+                f.write_str(&out)
+            }
+            // not valid JSON, the serializer refuses these
+            Repr::Float(value) => write!(f, "{}", value),
+            Repr::Lossless(ref lexeme) => f.write_str(lexeme),
+        }
+    }
+}
+
+impl fmt::Debug for JsonNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
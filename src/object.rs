@@ -0,0 +1,211 @@
+use crate::parser::JsonValue;
+use std::{cmp::Ordering, collections::HashMap, fmt, slice, vec};
+
+/// JSON object members in insertion order, with a hash index for O(1)
+/// lookup by key.
+///
+/// By default the parser keeps duplicate keys (see `DuplicateKeys`); lookups
+/// then see the first member with that key.
+#[derive(Clone, Default)]
+pub struct JsonObject {
+    entries: Vec<(String, JsonValue)>,
+    index: HashMap<String, usize>,
+}
+
+impl JsonObject {
+    pub fn new() -> Self {
+        JsonObject::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        JsonObject {
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.index
+            .get(key)
+            .map(|&position| &self.entries[position].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut JsonValue> {
+        match self.index.get(key) {
+            Some(&position) => Some(&mut self.entries[position].1),
+            None => None,
+        }
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Sets `key` to `value`. An existing member keeps its position and its
+    /// old value is returned, a new one is appended.
+    pub fn insert(&mut self, key: impl Into<String>, value: JsonValue) -> Option<JsonValue> {
+        let key = key.into();
+        match self.index.get(&key) {
+            Some(&position) => Some(std::mem::replace(&mut self.entries[position].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Appends a member even if `key` is already present.
+    pub(crate) fn push(&mut self, key: String, value: JsonValue) {
+        if !self.index.contains_key(&key) {
+            self.index.insert(key.clone(), self.entries.len());
+        }
+        self.entries.push((key, value));
+    }
+
+    /// Removes every member with `key`, keeping the order of the rest, and
+    /// returns the value lookups saw. This shifts later members, so it is
+    /// O(n).
+    pub fn remove(&mut self, key: &str) -> Option<JsonValue> {
+        let position = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(position);
+        let len = self.entries.len();
+        self.entries.retain(|(k, _)| k != key);
+        if self.entries.len() == len {
+            for later in self.index.values_mut() {
+                if *later > position {
+                    *later -= 1;
+                }
+            }
+        } else {
+            // dropped duplicates shift positions unevenly
+            self.reindex();
+        }
+        Some(value)
+    }
+
+    fn reindex(&mut self) {
+        self.index.clear();
+        for (position, (key, _)) in self.entries.iter().enumerate() {
+            if !self.index.contains_key(key) {
+                self.index.insert(key.clone(), position);
+            }
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &JsonValue> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut JsonValue> {
+        self.entries.iter_mut().map(|(_, value)| value)
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut JsonValue)> {
+        self.entries.iter_mut().map(|(key, value)| (&*key, value))
+    }
+}
+
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, (String, JsonValue)>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a String, &'a JsonValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, value)| (key, value))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl<'a> IntoIterator for &'a JsonObject {
+    type Item = (&'a String, &'a JsonValue);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for JsonObject {
+    type Item = (String, JsonValue);
+    type IntoIter = vec::IntoIter<(String, JsonValue)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// Later duplicates replace the value of the first, like `insert`.
+impl<K: Into<String>> FromIterator<(K, JsonValue)> for JsonObject {
+    fn from_iter<I: IntoIterator<Item = (K, JsonValue)>>(iter: I) -> Self {
+        let mut object = JsonObject::new();
+        for (key, value) in iter {
+            object.insert(key, value);
+        }
+        object
+    }
+}
+
+impl<K: Into<String>> Extend<(K, JsonValue)> for JsonObject {
+    fn extend<I: IntoIterator<Item = (K, JsonValue)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl From<Vec<(String, JsonValue)>> for JsonObject {
+    fn from(entries: Vec<(String, JsonValue)>) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+/// Objects are equal when they hold the same members in the same order.
+impl PartialEq for JsonObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl PartialOrd for JsonObject {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.entries.partial_cmp(&other.entries)
+    }
+}
+
+impl fmt::Debug for JsonObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
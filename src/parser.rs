@@ -1,69 +1,147 @@
 use crate::{
     error::{ParsedJsonError, ParserError},
     lexer::Lexer,
-    token::Token,
+    number::JsonNumber,
+    object::JsonObject,
+    token::{Span, SpannedToken, Token},
 };
-use std::{iter::Peekable, vec::IntoIter};
+use std::{collections::HashMap, iter::Peekable, vec::IntoIter};
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
 pub enum JsonValue {
-    Object(Vec<(String, JsonValue)>),
+    Object(JsonObject),
     Array(Vec<JsonValue>),
     String(String),
-    Number(f64),
+    Number(JsonNumber),
     Boolean(bool),
+    #[default]
     Null,
 }
 
 impl JsonValue {
     pub fn get(&self, key: &str) -> Result<&JsonValue, ParsedJsonError> {
         if let JsonValue::Object(ref obj) = *self {
-            for (k, v) in obj {
-                if k == key {
-                    return Ok(v);
-                }
-            }
-            Err(ParsedJsonError::KeyNotFound(key.to_string()))
+            obj.get(key)
+                .ok_or_else(|| ParsedJsonError::KeyNotFound(key.to_string()))
         } else {
-            Err(ParsedJsonError::UnexpectedType)
+            Err(self.unexpected_type("object"))
         }
     }
 
     pub fn get_string(&self) -> Result<&str, ParsedJsonError> {
-        if let JsonValue::String(ref s) = *self {
-            return Ok(s);
-        }
-        Err(ParsedJsonError::UnexpectedType)
+        self.as_str().ok_or_else(|| self.unexpected_type("string"))
     }
 
     pub fn get_number(&self) -> Result<f64, ParsedJsonError> {
-        if let JsonValue::Number(n) = *self {
-            return Ok(n);
-        }
-        Err(ParsedJsonError::UnexpectedType)
+        self.as_f64().ok_or_else(|| self.unexpected_type("number"))
     }
 
     pub fn get_boolean(&self) -> Result<bool, ParsedJsonError> {
-        if let JsonValue::Boolean(b) = *self {
-            return Ok(b);
+        self.as_bool()
+            .ok_or_else(|| self.unexpected_type("boolean"))
+    }
+
+    /// Name of the variant as used in error messages, e.g. `"string"`.
+    pub fn type_name(&self) -> &'static str {
+        match *self {
+            JsonValue::Object(_) => "object",
+            JsonValue::Array(_) => "array",
+            JsonValue::String(_) => "string",
+            JsonValue::Number(_) => "number",
+            JsonValue::Boolean(_) => "boolean",
+            JsonValue::Null => "null",
+        }
+    }
+
+    pub(crate) fn unexpected_type(&self, expected: &'static str) -> ParsedJsonError {
+        ParsedJsonError::UnexpectedType {
+            expected,
+            found: self.type_name(),
         }
-        Err(ParsedJsonError::UnexpectedType)
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(*self, JsonValue::Object(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(*self, JsonValue::Array(_))
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(*self, JsonValue::String(_))
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(*self, JsonValue::Number(_))
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        matches!(*self, JsonValue::Boolean(_))
     }
 
     pub fn is_null(&self) -> bool {
         matches!(*self, JsonValue::Null)
     }
 
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            JsonValue::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The number as `i64` if it is an integer that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            JsonValue::Number(ref n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// The number as `u64` if it is a non-negative integer that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            JsonValue::Number(ref n) => n.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// Any number as `f64`, rounding integers beyond 2^53.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            JsonValue::Number(ref n) => Some(n.as_f64()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            JsonValue::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match *self {
+            JsonValue::Array(ref array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&JsonObject> {
+        match *self {
+            JsonValue::Object(ref obj) => Some(obj),
+            _ => None,
+        }
+    }
+
     pub fn get_from_object(&self, key: &str) -> Result<&JsonValue, ParsedJsonError> {
         if let JsonValue::Object(ref obj) = *self {
-            for (k, v) in obj {
-                if k == key {
-                    return Ok(v);
-                }
-            }
-            Err(ParsedJsonError::KeyNotFound(key.to_string()))
+            obj.get(key)
+                .ok_or_else(|| ParsedJsonError::KeyNotFound(key.to_string()))
         } else {
-            Err(ParsedJsonError::UnexpectedType)
+            Err(self.unexpected_type("object"))
         }
     }
 
@@ -71,119 +149,379 @@ impl JsonValue {
         match *self {
             JsonValue::Object(ref obj) => Ok(obj.len()),
             JsonValue::Array(ref arr) => Ok(arr.len()),
-            _ => Err(ParsedJsonError::InvalidMethodCall(format!(
-                "len can be only perfomed with array and object type"
-            ))),
+            _ => Err(ParsedJsonError::InvalidMethodCall(
+                "len can be only perfomed with array and object type".to_string(),
+            )),
         }
     }
 
+    pub fn is_empty(&self) -> Result<bool, ParsedJsonError> {
+        Ok(self.len()? == 0)
+    }
+
     pub fn index(&self, index: usize) -> Result<&JsonValue, ParsedJsonError> {
         if let JsonValue::Array(ref array) = *self {
             return array
                 .get(index)
                 .ok_or(ParsedJsonError::IndexOutOfBounds(index));
         }
-        Err(ParsedJsonError::UnexpectedType)
+        Err(self.unexpected_type("array"))
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Result<&mut JsonValue, ParsedJsonError> {
+        if let JsonValue::Object(ref mut obj) = *self {
+            obj.get_mut(key)
+                .ok_or_else(|| ParsedJsonError::KeyNotFound(key.to_string()))
+        } else {
+            Err(self.unexpected_type("object"))
+        }
+    }
+
+    pub fn index_mut(&mut self, index: usize) -> Result<&mut JsonValue, ParsedJsonError> {
+        if let JsonValue::Array(ref mut array) = *self {
+            return array
+                .get_mut(index)
+                .ok_or(ParsedJsonError::IndexOutOfBounds(index));
+        }
+        Err(self.unexpected_type("array"))
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut JsonObject> {
+        match *self {
+            JsonValue::Object(ref mut obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<JsonValue>> {
+        match *self {
+            JsonValue::Array(ref mut array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Sets `key` on an object and returns the value it replaced, if any.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Result<Option<JsonValue>, ParsedJsonError> {
+        match *self {
+            JsonValue::Object(ref mut obj) => Ok(obj.insert(key, value)),
+            _ => Err(self.unexpected_type("object")),
+        }
+    }
+
+    /// Removes `key` from an object and returns its value.
+    pub fn remove(&mut self, key: &str) -> Result<JsonValue, ParsedJsonError> {
+        match *self {
+            JsonValue::Object(ref mut obj) => obj
+                .remove(key)
+                .ok_or_else(|| ParsedJsonError::KeyNotFound(key.to_string())),
+            _ => Err(self.unexpected_type("object")),
+        }
+    }
+
+    /// Appends `value` to an array.
+    pub fn push(&mut self, value: JsonValue) -> Result<(), ParsedJsonError> {
+        match *self {
+            JsonValue::Array(ref mut array) => {
+                array.push(value);
+                Ok(())
+            }
+            _ => Err(self.unexpected_type("array")),
+        }
+    }
+
+    /// Removes the last element of an array, `None` if it is empty.
+    pub fn pop(&mut self) -> Result<Option<JsonValue>, ParsedJsonError> {
+        match *self {
+            JsonValue::Array(ref mut array) => Ok(array.pop()),
+            _ => Err(self.unexpected_type("array")),
+        }
+    }
+
+    /// Moves the value out, leaving `Null` in its place.
+    pub fn take(&mut self) -> JsonValue {
+        std::mem::take(self)
+    }
+
+    /// Puts `value` in place of this one and returns the old value.
+    pub fn replace(&mut self, value: JsonValue) -> JsonValue {
+        std::mem::replace(self, value)
     }
 }
 
+/// Returned by indexing on a miss, so lookups can be chained.
+static NULL: JsonValue = JsonValue::Null;
+
+/// Yields `Null` when the key is missing or the value is not an object, so
+/// `value["a"]["b"][3]` never panics. Use `get` to tell a miss from a null.
 impl std::ops::Index<&str> for JsonValue {
     type Output = JsonValue;
 
     fn index(&self, key: &str) -> &Self::Output {
-        self.get(key).expect("Key not found in JSON object")
+        self.get(key).unwrap_or(&NULL)
     }
 }
 
+/// Yields `Null` when the index is out of bounds or the value is not an
+/// array. Use `index` to tell a miss from a null.
 impl std::ops::Index<usize> for JsonValue {
     type Output = JsonValue;
 
     fn index(&self, index: usize) -> &Self::Output {
-        self.index(index)
+        self.index(index).unwrap_or(&NULL)
+    }
+}
+
+/// Assigning to a missing key inserts it, so `value["key"] = ...` works on
+/// any object. `Null` becomes an empty object first, so `value["a"]["b"] =
+/// ...` creates `a` as needed. Panics on any other non-object value.
+impl std::ops::IndexMut<&str> for JsonValue {
+    fn index_mut(&mut self, key: &str) -> &mut Self::Output {
+        if self.is_null() {
+            *self = JsonValue::Object(JsonObject::new());
+        }
+        match *self {
+            JsonValue::Object(ref mut obj) => {
+                if !obj.contains_key(key) {
+                    obj.insert(key, JsonValue::Null);
+                }
+                obj.get_mut(key).expect("key was just inserted")
+            }
+            _ => panic!("Cannot index a non-object JSON value with a key"),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for JsonValue {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.index_mut(index)
             .expect("Index out of bounds in JSON array")
     }
 }
 
+/// What the parser does when an object repeats a key. RFC 8259 leaves this
+/// to the implementation, so producers and consumers that pick differently
+/// can disagree about what a document says.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Keep every member; lookups see the first one.
+    #[default]
+    KeepAll,
+    /// Keep the first member and drop later ones.
+    FirstWins,
+    /// Keep the value of the last member, at the position of the first.
+    LastWins,
+    /// Reject the document with `ParserError::DuplicateKey`.
+    Error,
+}
+
+/// Switches that relax how strictly the parser follows RFC 8259.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Tolerate a missing `,` between entries and a trailing `,` before the
+    /// closing bracket, for legacy data written by sloppy producers.
+    pub lenient_seperators: bool,
+    pub duplicate_keys: DuplicateKeys,
+}
+
 #[derive(Debug, Clone)]
 pub struct Parser {
-    tokens: Peekable<IntoIter<Token>>,
+    tokens: Peekable<IntoIter<SpannedToken>>,
+    options: ParserOptions,
+    end: Span,
 }
 impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Self {
+    /// `end` is where the input ended, as reported by `Lexer::end` after
+    /// `lex`, so errors about a truncated document point there.
+    pub fn new(tokens: Vec<SpannedToken>, end: Span) -> Self {
+        Parser::with_options(tokens, end, ParserOptions::default())
+    }
+    pub fn with_options(tokens: Vec<SpannedToken>, end: Span, options: ParserOptions) -> Self {
         let iterator = tokens.into_iter().peekable();
-        Parser { tokens: iterator }
+        Parser {
+            tokens: iterator,
+            options,
+            end,
+        }
     }
     fn current(&mut self) -> Option<&Token> {
-        return self.tokens.peek();
+        self.tokens.peek().map(|spanned| &spanned.token)
+    }
+    /// Span of the current token, or of the end of input once tokens run out.
+    fn current_span(&mut self) -> Span {
+        match self.tokens.peek() {
+            Some(spanned) => spanned.span,
+            None => self.end,
+        }
     }
     fn consume(&mut self) -> Option<Token> {
-        return self.tokens.next();
+        self.tokens.next().map(|spanned| spanned.token)
     }
     pub fn expect_token(&mut self, token: Token) -> Result<(), ParserError> {
-        if self.tokens.peek() == Some(&token) {
-            self.consume();
-            return Ok(());
-        } else {
-            return Err(ParserError::UnexpectedToken(token));
+        let span = self.current_span();
+        match self.consume() {
+            Some(found) if found == token => Ok(()),
+            Some(found) => Err(ParserError::UnexpectedToken(found, span)),
+            None => Err(ParserError::UnexpectedEndOfInput(span)),
         }
     }
     fn parse_value(&mut self) -> Result<JsonValue, ParserError> {
-        match self.current() {
-            Some(Token::BeginObject) => Ok(self.parse_object()?),
-            Some(Token::BeginArray) => Ok(self.parse_array()?),
-            Some(Token::ValueString(val)) => Ok(JsonValue::String(val.to_string())),
-            Some(Token::ValueNumber(val)) => Ok(JsonValue::Number(*val)),
-            Some(Token::ValueBoolean(val)) => Ok(JsonValue::Boolean(*val)),
+        let span = self.current_span();
+        match self.consume() {
+            Some(Token::BeginObject) => self.parse_object(),
+            Some(Token::BeginArray) => self.parse_array(),
+            Some(Token::ValueString(val)) => Ok(JsonValue::String(val)),
+            Some(Token::ValueNumber(val)) => Ok(JsonValue::Number(val)),
+            Some(Token::ValueBoolean(val)) => Ok(JsonValue::Boolean(val)),
             Some(Token::ValueNil) => Ok(JsonValue::Null),
-            ch => {
-                return Err(ParserError::UnexpectedToken(ch.unwrap().clone()));
+            // a separator or closing bracket where a value belongs, e.g. `[1,,2]`
+            Some(token) => Err(ParserError::ExpectedValue(token, span)),
+            None => Err(ParserError::UnexpectedEndOfInput(span)),
+        }
+    }
+
+    /// Handles what follows a member or element: a `,` or the closing `end`
+    /// token. In lenient mode the `,` may be left out and may also appear
+    /// right before `end`. Returns whether another entry follows.
+    fn parse_seperator(&mut self, end: Token) -> Result<bool, ParserError> {
+        let lenient = self.options.lenient_seperators;
+        let span = self.current_span();
+        match self.current() {
+            Some(Token::ValueSeperator) => {
+                self.consume();
+                if !lenient && self.current() == Some(&end) {
+                    return Err(ParserError::TrailingSeperator(end, span));
+                }
+                Ok(true)
+            }
+            Some(token) if *token == end => {
+                self.consume();
+                Ok(false)
             }
+            Some(Token::ValueString(_)) if lenient => Ok(true),
+            Some(Token::BeginObject | Token::BeginArray)
+            | Some(Token::ValueNumber(_) | Token::ValueBoolean(_) | Token::ValueNil)
+                if lenient && end == Token::EndArray =>
+            {
+                Ok(true)
+            }
+            Some(token) => Err(ParserError::ExpectedSeperatorOrEnd(
+                end,
+                token.clone(),
+                span,
+            )),
+            None => Err(ParserError::UnexpectedEndOfInput(span)),
         }
     }
 
+    // called with the opening `{` already consumed
     fn parse_object(&mut self) -> Result<JsonValue, ParserError> {
-        let mut object = Vec::new();
-        self.consume();
-        while self.current() != Some(&Token::EndObject) {
-            if let Some(Token::ValueString(key)) = self.consume() {
-                // we need to consume if true else need to panic
-                self.expect_token(Token::NameSeperator)?;
-                let value = self.parse_value()?;
-                object.push((key, value));
-                self.consume();
-                if self.current() == Some(&Token::ValueSeperator) {
-                    self.consume();
+        let mut object = JsonObject::new();
+        // where each key was first seen, only tracked to report duplicates
+        let mut key_spans: HashMap<String, Span> = HashMap::new();
+        loop {
+            let key_span = self.current_span();
+            let key = match self.consume() {
+                Some(Token::EndObject) => break,
+                Some(Token::ValueString(key)) => key,
+                Some(token) => return Err(ParserError::ExpectedKey(token, key_span)),
+                None => return Err(ParserError::UnexpectedEndOfInput(key_span)),
+            };
+            let span = self.current_span();
+            match self.consume() {
+                Some(Token::NameSeperator) => {}
+                Some(token) => return Err(ParserError::ExpectedNameSeperator(token, span)),
+                None => return Err(ParserError::UnexpectedEndOfInput(span)),
+            }
+            let value = self.parse_value()?;
+            match self.options.duplicate_keys {
+                DuplicateKeys::KeepAll => object.push(key, value),
+                DuplicateKeys::FirstWins => {
+                    if !object.contains_key(&key) {
+                        object.push(key, value);
+                    }
+                }
+                DuplicateKeys::LastWins => {
+                    object.insert(key, value);
+                }
+                DuplicateKeys::Error => {
+                    if let Some(&first) = key_spans.get(&key) {
+                        return Err(ParserError::DuplicateKey(key, first, key_span));
+                    }
+                    key_spans.insert(key.clone(), key_span);
+                    object.push(key, value);
                 }
             }
+            if !self.parse_seperator(Token::EndObject)? {
+                break;
+            }
         }
         Ok(JsonValue::Object(object))
     }
 
+    // called with the opening `[` already consumed
     fn parse_array(&mut self) -> Result<JsonValue, ParserError> {
         let mut array = Vec::new();
-        self.consume();
-        while self.current() != Some(&Token::EndArray) {
+        loop {
+            if self.current() == Some(&Token::EndArray) {
+                self.consume();
+                break;
+            }
             let value = self.parse_value()?;
             array.push(value);
-            self.consume();
-            if self.current() == Some(&Token::ValueSeperator) {
-                self.consume();
+            if !self.parse_seperator(Token::EndArray)? {
+                break;
             }
         }
         Ok(JsonValue::Array(array))
     }
 
-    pub fn parse(&mut self) -> Result<JsonValue, ParserError> {
+    fn parse_root(&mut self) -> Result<JsonValue, ParserError> {
+        let span = self.current_span();
         match self.current() {
-            Some(Token::BeginObject) => self.parse_object(),
-            Some(Token::BeginArray) => self.parse_array(),
-            ch => Err(ParserError::UnexpectedStart(ch.unwrap().clone())),
+            Some(
+                token @ (Token::EndObject
+                | Token::EndArray
+                | Token::NameSeperator
+                | Token::ValueSeperator),
+            ) => Err(ParserError::UnexpectedStart(token.clone(), span)),
+            _ => self.parse_value(),
+        }
+    }
+
+    /// Parses one document. Any JSON value is accepted at the top level,
+    /// including a bare string, number, boolean or null, but nothing may
+    /// follow it.
+    pub fn parse(&mut self) -> Result<JsonValue, ParserError> {
+        let value = self.parse_root()?;
+        if self.current().is_some() {
+            return Err(ParserError::TrailingCharacters(self.current_span()));
+        }
+        Ok(value)
+    }
+
+    /// Parses a stream of concatenated top-level values such as
+    /// `{"a":1} {"b":2} 3`, returning them in order.
+    pub fn parse_stream(&mut self) -> Result<Vec<JsonValue>, ParserError> {
+        let mut values = Vec::new();
+        while self.current().is_some() {
+            values.push(self.parse_root()?);
         }
+        Ok(values)
     }
     pub fn parse_json(json: &str) -> Result<JsonValue, Box<dyn std::error::Error>> {
         let mut lexer = Lexer::new(json);
         let lex = lexer.lex()?;
-        let parserd_json = Parser::new(lex).parse()?;
+        let parserd_json = Parser::new(lex, lexer.end()).parse()?;
+        Ok(parserd_json)
+    }
+    pub fn parse_json_stream(json: &str) -> Result<Vec<JsonValue>, Box<dyn std::error::Error>> {
+        let mut lexer = Lexer::new(json);
+        let lex = lexer.lex()?;
+        let parserd_json = Parser::new(lex, lexer.end()).parse_stream()?;
         Ok(parserd_json)
     }
 }
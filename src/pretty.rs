@@ -0,0 +1,214 @@
+use crate::{
+    error::SerializerError,
+    parser::JsonValue,
+    serializer::{write_number, write_string},
+};
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    Lf,
+    CrLf,
+}
+
+impl Newline {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Layout of pretty printed JSON. The default indents with two spaces, uses
+/// `\n`, puts a space after `:` and keeps empty containers as `[]` and `{}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub indent: Indent,
+    pub newline: Newline,
+    pub space_after_colon: bool,
+    /// Print empty arrays and objects as `[]` and `{}` instead of spreading
+    /// the brackets over two lines.
+    pub inline_empty: bool,
+    /// When non-zero, arrays and objects whose one-line form still fits
+    /// within this many columns are kept on one line. A tab counts as one
+    /// column.
+    pub max_line_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent: Indent::Spaces(2),
+            newline: Newline::Lf,
+            space_after_colon: true,
+            inline_empty: true,
+            max_line_width: 0,
+        }
+    }
+}
+
+struct PrettyWriter<'a> {
+    options: &'a FormatOptions,
+    out: String,
+}
+
+impl PrettyWriter<'_> {
+    fn indent_width(&self) -> usize {
+        match self.options.indent {
+            Indent::Spaces(width) => width,
+            Indent::Tab => 1,
+        }
+    }
+
+    fn newline(&mut self, depth: usize) {
+        self.out.push_str(self.options.newline.as_str());
+        for _ in 0..depth {
+            match self.options.indent {
+                Indent::Spaces(width) => self.out.extend(std::iter::repeat_n(' ', width)),
+                Indent::Tab => self.out.push('\t'),
+            }
+        }
+    }
+
+    fn colon(&self) -> &'static str {
+        if self.options.space_after_colon {
+            ": "
+        } else {
+            ":"
+        }
+    }
+
+    /// One-line form of `value`, with a space after every `,`.
+    fn inline(&self, value: &JsonValue) -> Result<String, SerializerError> {
+        let mut out = String::new();
+        write_inline(&mut out, value, self.colon())?;
+        Ok(out)
+    }
+
+    /// Writes `value` at nesting `depth`. `column` is where the value starts
+    /// on its line and `trailing` the width of what follows it there.
+    fn write_value(
+        &mut self,
+        value: &JsonValue,
+        depth: usize,
+        column: usize,
+        trailing: usize,
+    ) -> Result<(), SerializerError> {
+        let (open, close, len) = match *value {
+            JsonValue::Object(ref object) => ('{', '}', object.len()),
+            JsonValue::Array(ref array) => ('[', ']', array.len()),
+            _ => {
+                let colon = self.colon();
+                return write_inline(&mut self.out, value, colon);
+            }
+        };
+        if len == 0 {
+            self.out.push(open);
+            if !self.options.inline_empty {
+                self.newline(depth);
+            }
+            self.out.push(close);
+            return Ok(());
+        }
+        if self.options.max_line_width > 0 {
+            let inline = self.inline(value)?;
+            if column + inline.chars().count() + trailing <= self.options.max_line_width {
+                self.out.push_str(&inline);
+                return Ok(());
+            }
+        }
+        let child_column = (depth + 1) * self.indent_width();
+        self.out.push(open);
+        match *value {
+            JsonValue::Object(ref object) => {
+                for (index, (key, value)) in object.iter().enumerate() {
+                    self.newline(depth + 1);
+                    let key_start = self.out.len();
+                    write_string(&mut self.out, key);
+                    self.out.push_str(self.colon());
+                    let key_width = self.out[key_start..].chars().count();
+                    let trailing = usize::from(index + 1 < len);
+                    self.write_value(value, depth + 1, child_column + key_width, trailing)?;
+                    if trailing > 0 {
+                        self.out.push(',');
+                    }
+                }
+            }
+            JsonValue::Array(ref array) => {
+                for (index, value) in array.iter().enumerate() {
+                    self.newline(depth + 1);
+                    let trailing = usize::from(index + 1 < len);
+                    self.write_value(value, depth + 1, child_column, trailing)?;
+                    if trailing > 0 {
+                        self.out.push(',');
+                    }
+                }
+            }
+            _ => {}
+        }
+        self.newline(depth);
+        self.out.push(close);
+        Ok(())
+    }
+}
+
+fn write_inline(out: &mut String, value: &JsonValue, colon: &str) -> Result<(), SerializerError> {
+    match *value {
+        JsonValue::Object(ref object) => {
+            out.push('{');
+            for (index, (key, value)) in object.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_string(out, key);
+                out.push_str(colon);
+                write_inline(out, value, colon)?;
+            }
+            out.push('}');
+        }
+        JsonValue::Array(ref array) => {
+            out.push('[');
+            for (index, value) in array.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_inline(out, value, colon)?;
+            }
+            out.push(']');
+        }
+        JsonValue::String(ref string) => write_string(out, string),
+        JsonValue::Number(ref number) => write_number(out, number)?,
+        JsonValue::Boolean(boolean) => out.push_str(if boolean { "true" } else { "false" }),
+        JsonValue::Null => out.push_str("null"),
+    }
+    Ok(())
+}
+
+impl JsonValue {
+    /// Serializes the value as indented JSON text laid out by `options`.
+    pub fn to_pretty_string(&self, options: &FormatOptions) -> Result<String, SerializerError> {
+        let mut writer = PrettyWriter {
+            options,
+            out: String::new(),
+        };
+        writer.write_value(self, 0, 0, 0)?;
+        Ok(writer.out)
+    }
+
+    pub fn write_pretty<W: io::Write>(
+        &self,
+        writer: &mut W,
+        options: &FormatOptions,
+    ) -> Result<(), SerializerError> {
+        let json = self.to_pretty_string(options)?;
+        writer.write_all(json.as_bytes())?;
+        Ok(())
+    }
+}
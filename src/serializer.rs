@@ -0,0 +1,97 @@
+use crate::{error::SerializerError, number::JsonNumber, parser::JsonValue};
+use std::{fmt, io};
+
+/// Appends `value` to `out` as a quoted JSON string, escaping only what
+/// RFC 8259 requires: `"`, `\` and the control characters.
+pub(crate) fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch < '\u{20}' => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+pub(crate) fn write_number(out: &mut String, number: &JsonNumber) -> Result<(), SerializerError> {
+    if !number.is_finite() {
+        return Err(SerializerError::NonFiniteNumber(number.as_f64()));
+    }
+    out.push_str(&number.to_string());
+    Ok(())
+}
+
+/// With `non_finite_as_null`, NaN and the infinities are written as `null`
+/// instead of failing.
+fn write_value(
+    out: &mut String,
+    value: &JsonValue,
+    non_finite_as_null: bool,
+) -> Result<(), SerializerError> {
+    match *value {
+        JsonValue::Object(ref object) => {
+            out.push('{');
+            for (index, (key, value)) in object.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, value, non_finite_as_null)?;
+            }
+            out.push('}');
+        }
+        JsonValue::Array(ref array) => {
+            out.push('[');
+            for (index, value) in array.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(out, value, non_finite_as_null)?;
+            }
+            out.push(']');
+        }
+        JsonValue::String(ref string) => write_string(out, string),
+        JsonValue::Number(ref number) if non_finite_as_null && !number.is_finite() => {
+            out.push_str("null")
+        }
+        JsonValue::Number(ref number) => write_number(out, number)?,
+        JsonValue::Boolean(boolean) => out.push_str(if boolean { "true" } else { "false" }),
+        JsonValue::Null => out.push_str("null"),
+    }
+    Ok(())
+}
+
+impl JsonValue {
+    /// Serializes the value as compact JSON text, keeping object members in
+    /// their original order.
+    pub fn to_json_string(&self) -> Result<String, SerializerError> {
+        let mut out = String::new();
+        write_value(&mut out, self, false)?;
+        Ok(out)
+    }
+
+    pub fn write_json<W: io::Write>(&self, writer: &mut W) -> Result<(), SerializerError> {
+        let json = self.to_json_string()?;
+        writer.write_all(json.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Compact JSON text. Never fails: NaN and the infinities are written as
+/// `null`, like `JSON.stringify` does. Use `to_json_string` to refuse them.
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut json = String::new();
+        write_value(&mut json, self, true).expect("only non-finite numbers can fail");
+        f.write_str(&json)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::error::SerializerError;
+use crate::parser::{JsonValue, Parser};
+
+fn canonical_number(bits: u64) -> Result<String, SerializerError> {
+    JsonValue::Number(f64::from_bits(bits).into()).to_canonical_string()
+}
+
+// RFC 8785, appendix B
+#[test]
+pub fn canonical_number_test_vectors() {
+    for (bits, expected) in [
+        (0x0000000000000000, "0"),
+        (0x8000000000000000, "0"),
+        (0x0000000000000001, "5e-324"),
+        (0x8000000000000001, "-5e-324"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0xffefffffffffffff, "-1.7976931348623157e+308"),
+        (0x4340000000000000, "9007199254740992"),
+        (0xc340000000000000, "-9007199254740992"),
+        (0x4430000000000000, "295147905179352830000"),
+        (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+        (0x44b52d02c7e14af6, "1e+23"),
+        (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+        (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x41b3de4355555553, "333333333.3333332"),
+        (0x41b3de4355555554, "333333333.33333325"),
+        (0x41b3de4355555555, "333333333.3333333"),
+        (0x41b3de4355555556, "333333333.3333334"),
+        (0x41b3de4355555557, "333333333.33333343"),
+        (0xbecbf647612f3696, "-0.0000033333333333333333"),
+        (0x43143ff3c1cb0959, "1424953923781206.2"),
+    ] {
+        assert_eq!(
+            canonical_number(bits).unwrap(),
+            expected,
+            "bits {:016x}",
+            bits
+        );
+    }
+}
+
+#[test]
+pub fn canonical_refuses_non_finite_numbers() {
+    for bits in [0x7fffffffffffffff, 0x7ff0000000000000, 0xfff0000000000000] {
+        assert!(matches!(
+            canonical_number(bits),
+            Err(SerializerError::NonFiniteNumber(_))
+        ));
+    }
+}
+
+// RFC 8785, section 3.2.2
+#[test]
+pub fn canonical_sample_document() {
+    let test_json = r#"{
+  "numbers": [333333333.33333329, 1E30, 4.50,
+              2e-3, 0.000000000000000000000000001],
+  "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+  "literals": [null, true, false]
+}"#;
+    let value = Parser::parse_json(test_json).unwrap();
+    assert_eq!(
+        value.to_canonical_string().unwrap(),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+}
+
+// RFC 8785, section 3.2.3
+#[test]
+pub fn canonical_sorts_keys_by_utf16() {
+    let test_json = r#"{
+  "€": "Euro Sign",
+  "\r": "Carriage Return",
+  "דּ": "Hebrew Letter Dalet With Dagesh",
+  "1": "One",
+  "😀": "Emoji: Grinning Face",
+  "\u0080": "Control",
+  "ö": "Latin Small Letter O With Diaeresis"
+}"#;
+    let value = Parser::parse_json(test_json).unwrap();
+    let canonical = Parser::parse_json(&value.to_canonical_string().unwrap()).unwrap();
+    let values: Vec<&str> = match canonical {
+        JsonValue::Object(ref object) => object
+            .iter()
+            .map(|(_, value)| value.get_string().unwrap())
+            .collect(),
+        _ => panic!("expected an object"),
+    };
+    assert_eq!(
+        values,
+        vec![
+            "Carriage Return",
+            "One",
+            "Control",
+            "Latin Small Letter O With Diaeresis",
+            "Euro Sign",
+            "Emoji: Grinning Face",
+            "Hebrew Letter Dalet With Dagesh",
+        ]
+    );
+}
+
+#[test]
+pub fn canonical_nested_and_large_integers() {
+    let test_json = r#"{"b": {"z": [], "a": {}}, "a": 18446744073709551615, "c": -0}"#;
+    let value = Parser::parse_json(test_json).unwrap();
+    assert_eq!(
+        value.to_canonical_string().unwrap(),
+        r#"{"a":18446744073709552000,"b":{"a":{},"z":[]},"c":0}"#
+    );
+    let mut buffer = Vec::new();
+    value.write_canonical(&mut buffer).unwrap();
+    assert_eq!(buffer, value.to_canonical_string().unwrap().as_bytes());
+}
+
+#[test]
+pub fn canonical_refuses_duplicate_keys() {
+    let value = Parser::parse_json(r#"{"b": 0, "a": 1, "a": 2}"#).unwrap();
+    assert!(matches!(
+        value.to_canonical_string(),
+        Err(SerializerError::DuplicateKey(ref key)) if key == "a"
+    ));
+    let nested = Parser::parse_json(r#"[{"x": {"k": 1, "k": 1}}]"#).unwrap();
+    let mut buffer = Vec::new();
+    assert!(matches!(
+        nested.write_canonical(&mut buffer),
+        Err(SerializerError::DuplicateKey(_))
+    ));
+}
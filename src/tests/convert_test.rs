@@ -0,0 +1,106 @@
+use crate::error::ParsedJsonError;
+use crate::lexer::{Lexer, LexerOptions};
+use crate::parser::{JsonValue, Parser};
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+pub fn convert_rust_values_into_json() {
+    assert_eq!(JsonValue::from(true), JsonValue::Boolean(true));
+    assert_eq!(JsonValue::from("a"), JsonValue::String("a".to_string()));
+    assert_eq!(JsonValue::from(String::from("b")).to_string(), r#""b""#);
+    assert_eq!(JsonValue::from(-5_i8).to_string(), "-5");
+    assert_eq!(JsonValue::from(7_u16), JsonValue::from(7_i64));
+    assert_eq!(JsonValue::from(usize::MAX).as_u64(), Some(u64::MAX));
+    assert_eq!(
+        JsonValue::from(u128::MAX).to_string(),
+        u128::MAX.to_string()
+    );
+    assert_eq!(
+        JsonValue::from(i128::MIN).to_string(),
+        i128::MIN.to_string()
+    );
+    assert_eq!(JsonValue::from(1.5_f32).to_string(), "1.5");
+    assert_eq!(JsonValue::from(0.1_f64).to_string(), "0.1");
+    assert_eq!(JsonValue::from(None::<bool>), JsonValue::Null);
+    assert_eq!(JsonValue::from(Some(1_u8)).to_string(), "1");
+    assert_eq!(JsonValue::from(vec![1, 2]).to_string(), "[1,2]");
+    assert_eq!(JsonValue::from(&["x", "y"][..]).to_string(), r#"["x","y"]"#);
+    assert_eq!(JsonValue::from([true, false]).to_string(), "[true,false]");
+
+    let mut tree = BTreeMap::new();
+    tree.insert("b".to_string(), vec![Some(1), None]);
+    tree.insert("a".to_string(), vec![]);
+    assert_eq!(
+        JsonValue::from(tree).to_string(),
+        r#"{"a":[],"b":[1,null]}"#
+    );
+    let mut map = HashMap::new();
+    map.insert("k".to_string(), "v");
+    assert_eq!(JsonValue::from(map).to_string(), r#"{"k":"v"}"#);
+}
+
+#[test]
+pub fn convert_json_back_into_rust_values() {
+    let value = Parser::parse_json(
+        r#"{"b": true, "s": "text", "n": 300, "f": 0.5, "list": [1, null], "map": {"x": 1}}"#,
+    )
+    .unwrap();
+    assert!(bool::try_from(&value["b"]).unwrap());
+    assert_eq!(<&str>::try_from(&value["s"]).unwrap(), "text");
+    assert_eq!(String::try_from(&value["s"]).unwrap(), "text");
+    assert_eq!(u16::try_from(&value["n"]).unwrap(), 300);
+    assert_eq!(i128::try_from(&value["n"]).unwrap(), 300);
+    assert_eq!(f64::try_from(&value["f"]).unwrap(), 0.5);
+    assert_eq!(f32::try_from(&value["n"]).unwrap(), 300.0);
+    assert_eq!(
+        Vec::<Option<u8>>::try_from(&value["list"]).unwrap(),
+        [Some(1), None]
+    );
+    assert_eq!(
+        BTreeMap::<String, i32>::try_from(&value["map"]).unwrap()["x"],
+        1
+    );
+    assert_eq!(
+        HashMap::<String, u64>::try_from(&value["map"]).unwrap()["x"],
+        1
+    );
+}
+
+#[test]
+pub fn convert_checks_integer_ranges() {
+    let value = Parser::parse_json("[300, -1, 1.5, 18446744073709551615]").unwrap();
+    assert_eq!(
+        u8::try_from(&value[0]).unwrap_err().to_string(),
+        "Number 300 does not fit in u8"
+    );
+    assert!(matches!(
+        u32::try_from(&value[1]),
+        Err(ParsedJsonError::NumberOutOfRange(_, "u32"))
+    ));
+    assert!(i64::try_from(&value[2]).is_err());
+    assert!(i64::try_from(&value[3]).is_err());
+    assert_eq!(u64::try_from(&value[3]).unwrap(), u64::MAX);
+    assert!(matches!(
+        u8::try_from(&JsonValue::from("1")),
+        Err(ParsedJsonError::UnexpectedType {
+            expected: "number",
+            found: "string"
+        })
+    ));
+    assert!(Vec::<u8>::try_from(&value).is_err());
+}
+
+#[test]
+pub fn convert_lossless_integers_beyond_64_bits() {
+    let options = LexerOptions {
+        lossless_numbers: true,
+        ..LexerOptions::default()
+    };
+    let mut lexer = Lexer::with_options("[170141183460469231731687303715884105727, 2.0]", options);
+    let value = Parser::new(lexer.lex().unwrap(), lexer.end())
+        .parse()
+        .unwrap();
+    assert_eq!(i128::try_from(&value[0]).unwrap(), i128::MAX);
+    assert!(u64::try_from(&value[0]).is_err());
+    assert!(i32::try_from(&value[1]).is_err());
+}
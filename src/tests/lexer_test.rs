@@ -1,12 +1,22 @@
-use crate::lexer::Lexer;
-use crate::token::Token;
+use crate::error::LexerError;
+use crate::lexer::{Lexer, LexerOptions};
+use crate::token::{Span, Token};
+
+fn lex_tokens(lexer: &mut Lexer) -> Vec<Token> {
+    lexer
+        .lex()
+        .unwrap()
+        .into_iter()
+        .map(|spanned| spanned.token)
+        .collect()
+}
 
 #[test]
 pub fn lex_valid_empty_delimitter_check() {
     let test_json = r#"{}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(Token::BeginObject, Token::EndObject)
     );
 }
@@ -18,7 +28,7 @@ pub fn invalid_key_check() {
     println!("{}", test_json);
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec![
             Token::BeginObject,
             Token::ValueString("name".to_string()),
@@ -32,7 +42,7 @@ fn valid_one_key_one_value_string() {
     let test_json = r#"{"name" : "Bipul"}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("name".to_string()),
@@ -48,12 +58,12 @@ fn valid_one_key_one_value_number() {
     let test_json = r#"{"number" : 100}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("number".to_string()),
             Token::NameSeperator,
-            Token::ValueNumber(100 as f64),
+            Token::ValueNumber(100_u64.into()),
             Token::EndObject
         )
     )
@@ -64,7 +74,7 @@ fn valid_one_key_one_value_boolean_false() {
     let test_json = r#"{"boolean" : false}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("boolean".to_string()),
@@ -80,7 +90,7 @@ fn valid_one_key_one_value_boolean_true() {
     let test_json = r#"{"boolean" : true}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("boolean".to_string()),
@@ -96,7 +106,7 @@ fn valid_one_key_one_value_null() {
     let test_json = r#"{"value" : null}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("value".to_string()),
@@ -112,20 +122,20 @@ fn valid_one_key_array_value() {
     let test_json = r#"{"foo":[1,2,{"bar":2}]}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("foo".to_string()),
             Token::NameSeperator,
             Token::BeginArray,
-            Token::ValueNumber(1 as f64),
+            Token::ValueNumber(1_u64.into()),
             Token::ValueSeperator,
-            Token::ValueNumber(2 as f64),
+            Token::ValueNumber(2_u64.into()),
             Token::ValueSeperator,
             Token::BeginObject,
             Token::ValueString("bar".to_string()),
             Token::NameSeperator,
-            Token::ValueNumber(2 as f64),
+            Token::ValueNumber(2_u64.into()),
             Token::EndObject,
             Token::EndArray,
             Token::EndObject,
@@ -138,7 +148,7 @@ pub fn lex_valid_nested_object() {
     let test_json =
         r#"{"name": "John", "age": 30, "address": {"city": "New York", "state": "NY"}}"#;
     let mut lexer = Lexer::new(test_json);
-    let tokens = lexer.lex().unwrap();
+    let tokens = lex_tokens(&mut lexer);
 
     let expected_tokens = vec![
         Token::BeginObject,
@@ -148,7 +158,7 @@ pub fn lex_valid_nested_object() {
         Token::ValueSeperator,
         Token::ValueString("age".to_string()),
         Token::NameSeperator,
-        Token::ValueNumber(30.0),
+        Token::ValueNumber(30_u64.into()),
         Token::ValueSeperator,
         Token::ValueString("address".to_string()),
         Token::NameSeperator,
@@ -172,7 +182,7 @@ pub fn lex_valid_empty_array() {
     let test_json = r#"[]"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(Token::BeginArray, Token::EndArray)
     );
 }
@@ -182,10 +192,10 @@ pub fn lex_valid_array_with_values() {
     let test_json = r#"[1, "two", true, null]"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginArray,
-            Token::ValueNumber(1 as f64),
+            Token::ValueNumber(1_u64.into()),
             Token::ValueSeperator,
             Token::ValueString("two".to_string()),
             Token::ValueSeperator,
@@ -202,7 +212,7 @@ pub fn lex_invalid_missing_comma() {
     let test_json = r#"{"key1": "value1" "key2": "value2"}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("key1".to_string()),
@@ -221,7 +231,7 @@ pub fn lex_valid_mixed_array() {
     let test_json = r#"[{"key": "value"}, [1, 2, 3], true]"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginArray,
             Token::BeginObject,
@@ -231,11 +241,11 @@ pub fn lex_valid_mixed_array() {
             Token::EndObject,
             Token::ValueSeperator,
             Token::BeginArray,
-            Token::ValueNumber(1 as f64),
+            Token::ValueNumber(1_u64.into()),
             Token::ValueSeperator,
-            Token::ValueNumber(2 as f64),
+            Token::ValueNumber(2_u64.into()),
             Token::ValueSeperator,
-            Token::ValueNumber(3 as f64),
+            Token::ValueNumber(3_u64.into()),
             Token::EndArray,
             Token::ValueSeperator,
             Token::ValueBoolean(true),
@@ -246,17 +256,21 @@ pub fn lex_valid_mixed_array() {
 
 #[test]
 pub fn lex_invalid_unterminated_string() {
-    let test_json = r#"{"key": "value"#;
-    let mut lexer = Lexer::new(test_json);
-    assert_eq!(
-        lexer.lex().unwrap(),
-        vec!(
-            Token::BeginObject,
-            Token::ValueString("key".to_string()),
-            Token::NameSeperator,
-            Token::ValueString("value".to_string())
-        )
-    )
+    for test_json in [
+        r#"{"key": "value"#,
+        r#"{"key": "value\"#,
+        r#"{"key": "val\"#,
+        r#"{"key": "\u00"#,
+        r#"{"key": "\uD83D\uDE"#,
+    ] {
+        let mut lexer = Lexer::new(test_json);
+        match lexer.lex() {
+            Err(LexerError::UnterminatedString(span)) => {
+                assert_eq!(span, Span::new(8, test_json.len(), 1, 9))
+            }
+            other => panic!("expected unterminated string, got {:?}", other),
+        }
+    }
 }
 
 #[test]
@@ -264,7 +278,7 @@ pub fn lex_valid_escaped_characters() {
     let test_json = r#"{"escaped": "Line1\nLine2"}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("escaped".to_string()),
@@ -280,7 +294,7 @@ pub fn lex_invalid_extra_comma() {
     let test_json = r#"{"key": "value",}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("key".to_string()),
@@ -297,7 +311,7 @@ pub fn lex_valid_object_with_multiple_data_types() {
     let test_json = r#"{"string": "value", "number": 42, "boolean": false, "null_value": null}"#;
     let mut lexer = Lexer::new(test_json);
     assert_eq!(
-        lexer.lex().unwrap(),
+        lex_tokens(&mut lexer),
         vec!(
             Token::BeginObject,
             Token::ValueString("string".to_string()),
@@ -306,7 +320,7 @@ pub fn lex_valid_object_with_multiple_data_types() {
             Token::ValueSeperator,
             Token::ValueString("number".to_string()),
             Token::NameSeperator,
-            Token::ValueNumber(42 as f64),
+            Token::ValueNumber(42_u64.into()),
             Token::ValueSeperator,
             Token::ValueString("boolean".to_string()),
             Token::NameSeperator,
@@ -319,3 +333,306 @@ pub fn lex_valid_object_with_multiple_data_types() {
         )
     )
 }
+
+#[test]
+pub fn lex_token_spans() {
+    let test_json = "{\n  \"név\": 10,\n  \"b\": true\n}";
+    let mut lexer = Lexer::new(test_json);
+    let spans: Vec<Span> = lexer
+        .lex()
+        .unwrap()
+        .into_iter()
+        .map(|spanned| spanned.span)
+        .collect();
+    assert_eq!(
+        spans,
+        vec!(
+            Span::new(0, 1, 1, 1),
+            Span::new(4, 10, 2, 3),
+            Span::new(10, 11, 2, 8),
+            Span::new(12, 14, 2, 10),
+            Span::new(14, 15, 2, 12),
+            Span::new(18, 21, 3, 3),
+            Span::new(21, 22, 3, 6),
+            Span::new(23, 27, 3, 8),
+            Span::new(28, 29, 4, 1),
+        )
+    )
+}
+
+#[test]
+pub fn lex_invalid_character_reports_span() {
+    let test_json = "{\n  \"key\": @}";
+    let mut lexer = Lexer::new(test_json);
+    match lexer.lex() {
+        Err(LexerError::UnexpectedCharacter('@', span)) => {
+            assert_eq!(span, Span::new(11, 12, 2, 10))
+        }
+        other => panic!("expected unexpected character error, got {:?}", other),
+    }
+}
+
+#[test]
+pub fn lex_valid_all_simple_escapes() {
+    let test_json = r#"["\"\\\/\b\f\n\r\t"]"#;
+    let mut lexer = Lexer::new(test_json);
+    assert_eq!(
+        lex_tokens(&mut lexer),
+        vec!(
+            Token::BeginArray,
+            Token::ValueString("\"\\/\u{8}\u{c}\n\r\t".to_string()),
+            Token::EndArray,
+        )
+    )
+}
+
+#[test]
+pub fn lex_valid_unicode_escapes() {
+    let test_json = r#"["\u0041\u00e9\u20AC", "\uD83D\uDE00"]"#;
+    let mut lexer = Lexer::new(test_json);
+    assert_eq!(
+        lex_tokens(&mut lexer),
+        vec!(
+            Token::BeginArray,
+            Token::ValueString("Aé€".to_string()),
+            Token::ValueSeperator,
+            Token::ValueString("😀".to_string()),
+            Token::EndArray,
+        )
+    )
+}
+
+#[test]
+pub fn lex_invalid_unicode_escape_digit() {
+    let test_json = r#"["\u12G4"]"#;
+    let mut lexer = Lexer::new(test_json);
+    assert!(matches!(
+        lexer.lex(),
+        Err(LexerError::InvalidEscapeSquence('G', _))
+    ));
+}
+
+#[test]
+pub fn lex_invalid_lone_surrogates() {
+    for test_json in [
+        r#"["\uD83D"]"#,
+        r#"["\uDE00"]"#,
+        r#"["\uD83Dx"]"#,
+        r#"["\uD83D\u0041"]"#,
+    ] {
+        let mut lexer = Lexer::new(test_json);
+        match lexer.lex() {
+            Err(LexerError::LoneSurrogate(_, span)) => assert_eq!(span, Span::new(2, 8, 1, 3)),
+            other => panic!("expected lone surrogate error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+pub fn lex_lossy_lone_surrogates() {
+    let test_json = r#"["\uD83Dx", "\uDE00", "\uD83D\u0041", "\uD83D\uD83D\uDE00"]"#;
+    let options = LexerOptions {
+        lossy_surrogates: true,
+        ..LexerOptions::default()
+    };
+    let mut lexer = Lexer::with_options(test_json, options);
+    assert_eq!(
+        lex_tokens(&mut lexer),
+        vec!(
+            Token::BeginArray,
+            Token::ValueString("\u{FFFD}x".to_string()),
+            Token::ValueSeperator,
+            Token::ValueString("\u{FFFD}".to_string()),
+            Token::ValueSeperator,
+            Token::ValueString("\u{FFFD}A".to_string()),
+            Token::ValueSeperator,
+            Token::ValueString("\u{FFFD}😀".to_string()),
+            Token::EndArray,
+        )
+    )
+}
+
+#[test]
+pub fn lex_valid_numbers() {
+    let test_json = r#"[0, -0, 12, -3.25, 1e3, 1E+2, 2.5e-1, 0.5]"#;
+    let mut lexer = Lexer::new(test_json);
+    let numbers: Vec<Token> = lex_tokens(&mut lexer)
+        .into_iter()
+        .filter(|token| matches!(token, Token::ValueNumber(_)))
+        .collect();
+    assert_eq!(
+        numbers,
+        vec!(
+            Token::ValueNumber(0_u64.into()),
+            Token::ValueNumber((-0.0_f64).into()),
+            Token::ValueNumber(12_u64.into()),
+            Token::ValueNumber((-3.25_f64).into()),
+            Token::ValueNumber(1000.0_f64.into()),
+            Token::ValueNumber(100.0_f64.into()),
+            Token::ValueNumber(0.25_f64.into()),
+            Token::ValueNumber(0.5_f64.into()),
+        )
+    )
+}
+
+#[test]
+pub fn lex_invalid_numbers() {
+    for (test_json, lexeme) in [
+        ("[1-2]", "1-2"),
+        ("[--5]", "--5"),
+        ("[01]", "01"),
+        ("[1e]", "1e"),
+        ("[1.]", "1."),
+        ("[1.e5]", "1.e5"),
+        ("[-]", "-"),
+        ("[1e+]", "1e+"),
+        ("[2.5.1]", "2.5.1"),
+    ] {
+        let mut lexer = Lexer::new(test_json);
+        match lexer.lex() {
+            Err(LexerError::InvalidNumber(found, span)) => {
+                assert_eq!(found, lexeme);
+                assert_eq!(span, Span::new(1, 1 + lexeme.len(), 1, 2));
+            }
+            other => panic!("expected invalid number for {}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn lex_valid_all_whitespace() {
+    let test_json = "{\r\n\t\"key\":\t[1,\r\n\t\t2]\r\n}";
+    let mut lexer = Lexer::new(test_json);
+    assert_eq!(
+        lex_tokens(&mut lexer),
+        vec!(
+            Token::BeginObject,
+            Token::ValueString("key".to_string()),
+            Token::NameSeperator,
+            Token::BeginArray,
+            Token::ValueNumber(1_u64.into()),
+            Token::ValueSeperator,
+            Token::ValueNumber(2_u64.into()),
+            Token::EndArray,
+            Token::EndObject,
+        )
+    )
+}
+
+#[test]
+pub fn lex_byte_order_mark() {
+    let test_json = "\u{feff}[]";
+    let mut lexer = Lexer::new(test_json);
+    assert!(matches!(
+        lexer.lex(),
+        Err(LexerError::UnexpectedCharacter('\u{feff}', _))
+    ));
+
+    let options = LexerOptions {
+        skip_bom: true,
+        ..LexerOptions::default()
+    };
+    let mut lexer = Lexer::with_options(test_json, options);
+    let tokens = lexer.lex().unwrap();
+    assert_eq!(tokens[0].token, Token::BeginArray);
+    assert_eq!(tokens[0].span, Span::new(3, 4, 1, 1));
+}
+
+#[test]
+pub fn lex_invalid_unicode_whitespace() {
+    for (test_json, whitespace) in [
+        ("[1,\u{a0}2]", '\u{a0}'),
+        ("[\u{2003}]", '\u{2003}'),
+        ("[\u{2028}]", '\u{2028}'),
+    ] {
+        let mut lexer = Lexer::new(test_json);
+        match lexer.lex() {
+            Err(LexerError::UnsupportedWhitespace(found, _)) => assert_eq!(found, whitespace),
+            other => panic!("expected whitespace error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+pub fn lex_valid_literals_at_delimiters() {
+    let test_json = "[true,false]\t{\"a\":null}\nnull";
+    let mut lexer = Lexer::new(test_json);
+    assert_eq!(
+        lex_tokens(&mut lexer),
+        vec!(
+            Token::BeginArray,
+            Token::ValueBoolean(true),
+            Token::ValueSeperator,
+            Token::ValueBoolean(false),
+            Token::EndArray,
+            Token::BeginObject,
+            Token::ValueString("a".to_string()),
+            Token::NameSeperator,
+            Token::ValueNil,
+            Token::EndObject,
+            Token::ValueNil,
+        )
+    )
+}
+
+#[test]
+pub fn lex_invalid_literals() {
+    for (test_json, literal, start) in [
+        ("[trueX]", "trueX", 1),
+        ("[nul", "nul", 1),
+        ("[tru,]", "tru", 1),
+        ("[1, falsey]", "falsey", 4),
+        ("[True]", "True", 1),
+        ("{\"a\": nulll}", "nulll", 6),
+        ("[null1]", "null1", 1),
+    ] {
+        let mut lexer = Lexer::new(test_json);
+        match lexer.lex() {
+            Err(LexerError::InvalidLiteral(found, span)) => {
+                assert_eq!(found, literal);
+                assert_eq!((span.start, span.end), (start, start + literal.len()));
+            }
+            other => panic!(
+                "expected invalid literal for {}, got {:?}",
+                test_json, other
+            ),
+        }
+    }
+}
+
+#[test]
+pub fn lex_invalid_control_characters_in_string() {
+    for (test_json, control) in [
+        ("[\"a\nb\"]", '\n'),
+        ("[\"tab\there\"]", '\t'),
+        ("[\"\u{0}\"]", '\u{0}'),
+        ("[\"\u{1f}\"]", '\u{1f}'),
+    ] {
+        let mut lexer = Lexer::new(test_json);
+        match lexer.lex() {
+            Err(LexerError::UnescapedControlCharacter(found, span)) => {
+                assert_eq!(found, control);
+                assert_eq!(span.end, span.start + 1);
+            }
+            other => panic!("expected control character error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+pub fn lex_allowed_control_characters_in_string() {
+    let test_json = "[\"a\nb\tc\u{7f}\"]";
+    let options = LexerOptions {
+        allow_control_characters: true,
+        ..LexerOptions::default()
+    };
+    let mut lexer = Lexer::with_options(test_json, options);
+    assert_eq!(
+        lex_tokens(&mut lexer),
+        vec!(
+            Token::BeginArray,
+            Token::ValueString("a\nb\tc\u{7f}".to_string()),
+            Token::EndArray,
+        )
+    )
+}
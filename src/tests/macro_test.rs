@@ -0,0 +1,56 @@
+use crate::parser::{JsonValue, Parser};
+
+#[test]
+pub fn json_macro_literals() {
+    assert_eq!(json!(null), JsonValue::Null);
+    assert_eq!(json!(true), JsonValue::Boolean(true));
+    assert_eq!(json!("text"), JsonValue::String("text".to_string()));
+    assert_eq!(json!(-1.5).to_string(), "-1.5");
+    assert_eq!(json!([]).to_string(), "[]");
+    assert_eq!(json!({}).to_string(), "{}");
+}
+
+#[test]
+pub fn json_macro_nested_matches_parser() {
+    let value = json!({
+        "name": "John",
+        "age": 30,
+        "tags": ["a", true, null, [1, [2]], {"k": {}}],
+        "nested": {"deep": {"deeper": [false]}}
+    });
+    let parsed = Parser::parse_json(
+        r#"{"name": "John", "age": 30, "tags": ["a", true, null, [1, [2]], {"k": {}}],
+            "nested": {"deep": {"deeper": [false]}}}"#,
+    )
+    .unwrap();
+    assert_eq!(value, parsed);
+}
+
+#[test]
+pub fn json_macro_trailing_commas() {
+    let value = json!({
+        "list": [1, 2,],
+        "object": {"a": 1,},
+    });
+    assert_eq!(value.to_string(), r#"{"list":[1,2],"object":{"a":1}}"#);
+}
+
+#[test]
+pub fn json_macro_expressions_as_values_and_keys() {
+    let id = 41;
+    let name = String::from("Ann");
+    let key = "dynamic";
+    let tags = vec!["x", "y"];
+    let value = json!({
+        "id": id + 1,
+        "name": name.clone(),
+        key: tags,
+        format!("k{}", id): if id > 40 { "big" } else { "small" },
+        "maybe": None::<i32>,
+        "items": [id, id as f64 / 2.0, name.len(), json!({"inner": id})],
+    });
+    assert_eq!(
+        value.to_string(),
+        r#"{"id":42,"name":"Ann","dynamic":["x","y"],"k41":"big","maybe":null,"items":[41,20.5,3,{"inner":41}]}"#
+    );
+}
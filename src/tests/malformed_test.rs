@@ -0,0 +1,133 @@
+use crate::error::ParserError;
+use crate::lexer::Lexer;
+use crate::parser::{JsonValue, Parser};
+use crate::token::Token;
+use std::{sync::mpsc, thread, time::Duration};
+
+/// Parses on a worker thread so a regression that loops forever fails the
+/// test instead of hanging the whole run.
+fn parse_with_timeout(test_json: &'static str) -> Result<JsonValue, ParserError> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut lexer = Lexer::new(test_json);
+        let tokens = lexer.lex().unwrap();
+        let result = Parser::new(tokens, lexer.end()).parse();
+        let _ = sender.send(result);
+    });
+    receiver
+        .recv_timeout(Duration::from_secs(1))
+        .unwrap_or_else(|_| panic!("parsing {:?} did not finish", test_json))
+}
+
+#[test]
+pub fn malformed_truncated_documents_fail() {
+    for test_json in [
+        "",
+        "{",
+        "[",
+        r#"{"a""#,
+        r#"{"a":"#,
+        r#"{"a": 1"#,
+        r#"{"a": 1,"#,
+        "[1, 2",
+        "[1, 2,",
+        r#"[{"a": [1, {"b": 2"#,
+    ] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::UnexpectedEndOfInput(_)) => {}
+            other => panic!("expected end of input for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_object_keys_fail() {
+    for test_json in [r#"{1: 2}"#, r#"{null: 2}"#, r#"{"a": 1, [2]: 3}"#, "{:}"] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::ExpectedKey(_, _)) => {}
+            other => panic!("expected key error for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_missing_colon_fails() {
+    for test_json in [r#"{"a" 1}"#, r#"{"a", "b"}"#, r#"{"a"}"#] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::ExpectedNameSeperator(_, _)) => {}
+            other => panic!("expected ':' error for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_missing_seperator_or_end_fails() {
+    for test_json in [r#"{"a": 1 : 2}"#, "[1 : 2]", r#"{"a": 1]"#, "[1}"] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::ExpectedSeperatorOrEnd(_, _, _)) => {}
+            other => panic!("expected ',' error for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_values_fail() {
+    for test_json in ["[:]", "[,1]", r#"{"a": }"#, r#"{"a": ,}"#] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::ExpectedValue(_, _)) => {}
+            other => panic!("expected bad value for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_strict_missing_seperators_fail() {
+    for test_json in [
+        "[1 2 3]",
+        r#"{"a":1 "b":2}"#,
+        r#"[{"a": 1} {"b": 2}]"#,
+        "[[] []]",
+    ] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::ExpectedSeperatorOrEnd(_, _, _)) => {}
+            other => panic!("expected ',' error for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_strict_extra_seperators_fail() {
+    for test_json in ["[1,]", r#"{"a": 1,}"#, "[[1],]"] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::TrailingSeperator(_, _)) => {}
+            other => panic!("expected trailing ',' for {:?}, got {:?}", test_json, other),
+        }
+    }
+    for (test_json, start) in [("[1,,2]", 3), ("[,]", 1), (r#"{"a": ,}"#, 6)] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::ExpectedValue(Token::ValueSeperator, span)) => {
+                assert_eq!(span.start, start)
+            }
+            other => panic!("expected a value for {:?}, got {:?}", test_json, other),
+        }
+    }
+}
+
+#[test]
+pub fn malformed_trailing_characters_fail() {
+    for (test_json, start) in [
+        (r#"{"a":1}}}]"#, 7),
+        ("{} {}", 3),
+        ("[1] 2", 4),
+        (r#""ok" ,"#, 5),
+        ("null null", 5),
+    ] {
+        match parse_with_timeout(test_json) {
+            Err(ParserError::TrailingCharacters(span)) => assert_eq!(span.start, start),
+            other => panic!(
+                "expected trailing characters for {:?}, got {:?}",
+                test_json, other
+            ),
+        }
+    }
+}
@@ -1,2 +1,12 @@
+pub mod canonical_test;
+pub mod convert_test;
 pub mod lexer_test;
+pub mod macro_test;
+pub mod malformed_test;
+pub mod mutation_test;
+pub mod number_test;
+pub mod object_test;
 pub mod parser_test;
+pub mod pretty_test;
+pub mod serializer_test;
+pub mod typed_test;
@@ -0,0 +1,87 @@
+use crate::error::ParsedJsonError;
+use crate::parser::{JsonValue, Parser};
+
+#[test]
+pub fn mutate_through_get_mut_and_index_mut() {
+    let mut value = Parser::parse_json(r#"{"user": {"name": "Ann"}, "tags": [1, 2]}"#).unwrap();
+    *value.get_mut("user").unwrap().get_mut("name").unwrap() = JsonValue::String("Bob".to_string());
+    *value.get_mut("tags").unwrap().index_mut(1).unwrap() = JsonValue::Null;
+    assert_eq!(
+        value.to_string(),
+        r#"{"user":{"name":"Bob"},"tags":[1,null]}"#
+    );
+    assert!(matches!(
+        value.get_mut("missing"),
+        Err(ParsedJsonError::KeyNotFound(_))
+    ));
+    assert!(matches!(
+        value.get_mut("tags").unwrap().index_mut(5),
+        Err(ParsedJsonError::IndexOutOfBounds(5))
+    ));
+}
+
+#[test]
+pub fn mutate_objects_and_arrays() {
+    let mut value = Parser::parse_json(r#"{"a": 1, "list": []}"#).unwrap();
+    assert_eq!(value.insert("b", JsonValue::Boolean(true)).unwrap(), None);
+    assert!(value.insert("a", JsonValue::Null).unwrap().is_some());
+    assert_eq!(value.remove("b").unwrap(), JsonValue::Boolean(true));
+    assert!(value.remove("b").is_err());
+
+    let list = value.get_mut("list").unwrap();
+    list.push(JsonValue::Boolean(false)).unwrap();
+    list.push(JsonValue::Null).unwrap();
+    assert_eq!(list.pop().unwrap(), Some(JsonValue::Null));
+    list.as_array_mut().unwrap().push(JsonValue::Null);
+    assert!(list.as_object_mut().is_none());
+    assert!(list.insert("key", JsonValue::Null).is_err());
+
+    value
+        .as_object_mut()
+        .unwrap()
+        .insert("c", JsonValue::String("x".to_string()));
+    assert_eq!(
+        value.to_string(),
+        r#"{"a":null,"list":[false,null],"c":"x"}"#
+    );
+    assert!(value.push(JsonValue::Null).is_err());
+    assert!(value.pop().is_err());
+}
+
+#[test]
+pub fn take_and_replace() {
+    let mut value = Parser::parse_json(r#"{"a": [1]}"#).unwrap();
+    let taken = value["a"].take();
+    assert_eq!(taken.to_string(), "[1]");
+    assert_eq!(value.to_string(), r#"{"a":null}"#);
+    let old = value["a"].replace(JsonValue::Boolean(true));
+    assert!(old.is_null());
+    assert_eq!(value.to_string(), r#"{"a":true}"#);
+}
+
+#[test]
+pub fn index_mut_inserts_missing_keys() {
+    let mut value = Parser::parse_json(r#"{"a": 1, "list": [1, 2]}"#).unwrap();
+    value["b"] = JsonValue::String("new".to_string());
+    value["a"] = JsonValue::Null;
+    value["list"][0] = JsonValue::Boolean(true);
+    assert_eq!(value.to_string(), r#"{"a":null,"list":[true,2],"b":"new"}"#);
+}
+
+#[test]
+pub fn index_mut_creates_nested_objects_through_null() {
+    let mut value = json!({"a": null});
+    value["a"]["b"] = json!(1);
+    value["x"]["y"]["z"] = json!("deep");
+    assert_eq!(value.to_string(), r#"{"a":{"b":1},"x":{"y":{"z":"deep"}}}"#);
+    let mut value = JsonValue::Null;
+    value["k"] = json!(true);
+    assert_eq!(value.to_string(), r#"{"k":true}"#);
+}
+
+#[test]
+#[should_panic]
+pub fn index_mut_key_on_array_panics() {
+    let mut value = Parser::parse_json("[]").unwrap();
+    value["a"] = JsonValue::Null;
+}
@@ -0,0 +1,270 @@
+use crate::lexer::{Lexer, LexerOptions};
+use crate::number::JsonNumber;
+use crate::parser::Parser;
+use crate::token::Token;
+use std::cmp::Ordering;
+
+fn lex_number(text: &str, options: LexerOptions) -> JsonNumber {
+    let mut lexer = Lexer::with_options(text, options);
+    match lexer.lex().unwrap().remove(0).token {
+        Token::ValueNumber(number) => number,
+        token => panic!("expected a number token, got {:?}", token),
+    }
+}
+
+#[test]
+pub fn number_keeps_large_integers_exact() {
+    let number = lex_number("9007199254740993", LexerOptions::default());
+    assert_eq!(number.as_u64(), Some(9007199254740993));
+    assert_eq!(number.as_i64(), Some(9007199254740993));
+    assert_eq!(number.to_string(), "9007199254740993");
+
+    let number = lex_number("18446744073709551615", LexerOptions::default());
+    assert_eq!(number.as_u64(), Some(u64::MAX));
+    assert_eq!(number.as_i64(), None);
+
+    let number = lex_number("-9223372036854775808", LexerOptions::default());
+    assert_eq!(number.as_i64(), Some(i64::MIN));
+    assert_eq!(number.as_u64(), None);
+}
+
+#[test]
+pub fn number_float_accessors() {
+    let number = lex_number("19.99", LexerOptions::default());
+    assert!(number.is_f64());
+    assert_eq!(number.as_f64(), 19.99);
+    assert_eq!(number.as_i64(), None);
+    assert_eq!(number.as_u64(), None);
+    assert_eq!(number.as_str_lossless(), None);
+
+    // integers that overflow 64 bits fall back to f64
+    let number = lex_number("18446744073709551616", LexerOptions::default());
+    assert_eq!(number.as_u64(), None);
+    assert_eq!(number.as_f64(), 18446744073709551616.0);
+}
+
+#[test]
+pub fn number_display_round_trips() {
+    for text in [
+        "0", "-0.0", "42", "-17", "0.1", "19.99", "1e300", "1.5e-7", "2.0", "1e2", "-5.0",
+    ] {
+        let number = lex_number(text, LexerOptions::default());
+        let printed = number.to_string();
+        assert_eq!(lex_number(&printed, LexerOptions::default()), number);
+    }
+}
+
+#[test]
+pub fn number_lossless_keeps_lexeme() {
+    let options = LexerOptions {
+        lossless_numbers: true,
+        ..LexerOptions::default()
+    };
+    for text in [
+        "1.50",
+        "-0",
+        "1E+2",
+        "3.141592653589793238462643383279",
+        "123456789012345678901234567890",
+    ] {
+        let number = lex_number(text, options);
+        assert_eq!(number.as_str_lossless(), Some(text));
+        assert_eq!(number.to_string(), text);
+    }
+
+    let number = lex_number("12345678901234567", options);
+    assert_eq!(number.as_u64(), Some(12345678901234567));
+    assert_eq!(number.as_f64(), 12345678901234568.0);
+}
+
+#[test]
+pub fn number_ordering() {
+    let small = JsonNumber::from(-5_i64);
+    let large = JsonNumber::from(u64::MAX);
+    let half = JsonNumber::from(0.5_f64);
+    assert!(small < half);
+    assert!(half < large);
+    assert!(small < large);
+}
+
+#[test]
+pub fn number_float_formatting() {
+    for (value, expected) in [
+        (1.0, "1"),
+        (-250.0, "-250"),
+        (0.1, "0.1"),
+        (-0.0, "-0"),
+        (19.99, "19.99"),
+        (123456789.0, "123456789"),
+        (1e20, "100000000000000000000"),
+        (1e21, "1e+21"),
+        (1.5e300, "1.5e+300"),
+        (0.000001, "0.000001"),
+        (1.5e-7, "1.5e-7"),
+        (5e-324, "5e-324"),
+        (f64::MAX, "1.7976931348623157e+308"),
+        (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        (9007199254740993.0, "9007199254740992"),
+    ] {
+        assert_eq!(JsonNumber::from(value).to_string(), expected);
+    }
+}
+
+/// xorshift64, enough to spread test inputs over the whole bit space
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+pub fn number_float_formatting_round_trips_random_bits() {
+    let mut state = 0x9E37_79B9_7F4A_7C15;
+    for _ in 0..5_000 {
+        let value = f64::from_bits(next_random(&mut state));
+        if !value.is_finite() {
+            continue;
+        }
+        let printed = JsonNumber::from(value).to_string();
+        let parsed = lex_number(&printed, LexerOptions::default());
+        assert_eq!(
+            parsed.as_f64().to_bits(),
+            value.to_bits(),
+            "{:?} printed as {}",
+            value,
+            printed
+        );
+        // no shorter digit string reads back as the same value
+        let digits = printed
+            .trim_start_matches('-')
+            .split(['e', 'E'])
+            .next()
+            .unwrap()
+            .replace('.', "");
+        let significant = digits.trim_start_matches('0').trim_end_matches('0');
+        let std_digits = format!("{:e}", value.abs())
+            .split('e')
+            .next()
+            .unwrap()
+            .replace('.', "");
+        assert!(
+            significant.len() <= std_digits.len().max(1),
+            "{:?} printed as {}",
+            value,
+            printed
+        );
+    }
+}
+
+#[test]
+pub fn number_float_parsing_edge_cases() {
+    for text in [
+        "0.1",
+        "0.30000000000000004",
+        "-0.0",
+        "9007199254740993.0",
+        "2.2250738585072011e-308",
+        "2.2250738585072014e-308",
+        "4.9e-324",
+        "2.4703282292062327e-324",
+        "2.4703282292062328e-324",
+        "1.7976931348623157e308",
+        "1.7976931348623159e308",
+        "1e22",
+        "1e23",
+        "12345e30",
+        "8.988465674311579e307",
+        "7.2057594037927933e16",
+        "123456789012345678901234567890e-10",
+        "0.000000000000000000000000000000000000000001",
+        "1e400",
+        "1e-400",
+        "0e999999999999",
+        "3.14159265358979323846264338327950288419716939937510",
+    ] {
+        let number = lex_number(text, LexerOptions::default());
+        assert_eq!(
+            number.as_f64().to_bits(),
+            text.parse::<f64>().unwrap().to_bits(),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+pub fn number_float_parsing_matches_std_on_random_decimals() {
+    let mut state = 0x2545_F491_4F6C_DD1D;
+    for _ in 0..20_000 {
+        let digit_count = 1 + next_random(&mut state) % 25;
+        let mut text = String::new();
+        for index in 0..digit_count {
+            let digit = next_random(&mut state) % 10;
+            // keep the integer part free of leading zeros
+            let digit = if index == 0 && digit_count > 1 {
+                digit.max(1)
+            } else {
+                digit
+            };
+            text.push((b'0' + digit as u8) as char);
+        }
+        let point = next_random(&mut state) % (digit_count + 1);
+        if point > 0 && point < digit_count {
+            text.insert(point as usize, '.');
+        }
+        // half of the cases stay within reach of the fast path
+        let exponent = if next_random(&mut state).is_multiple_of(2) {
+            (next_random(&mut state) % 60) as i64 - 30
+        } else {
+            (next_random(&mut state) % 660) as i64 - 340
+        };
+        text.push_str(&format!("e{}", exponent));
+        let number = lex_number(&text, LexerOptions::default());
+        assert_eq!(
+            number.as_f64().to_bits(),
+            text.parse::<f64>().unwrap().to_bits(),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+pub fn number_equality_ignores_representation() {
+    let lossless = LexerOptions {
+        lossless_numbers: true,
+        ..LexerOptions::default()
+    };
+    for (a, b) in [("2", "2.0"), ("100", "1e2"), ("-5", "-5.0"), ("0", "-0")] {
+        let a = lex_number(a, LexerOptions::default());
+        let b = lex_number(b, LexerOptions::default());
+        assert_eq!(a, b);
+        assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+    }
+    assert_eq!(lex_number("1", lossless), JsonNumber::from(1_u64));
+    assert_eq!(lex_number("2.00", lossless), JsonNumber::from(2.0));
+    assert_eq!(lex_number("-3", lossless), JsonNumber::from(-3_i64));
+    assert_ne!(
+        lex_number("2.5", LexerOptions::default()),
+        JsonNumber::from(2_u64)
+    );
+    assert!(JsonNumber::from(2_u64) < lex_number("2.5", LexerOptions::default()));
+    assert!(JsonNumber::from(-3_i64) < JsonNumber::from(-2.0));
+    assert_ne!(JsonNumber::from(f64::NAN), JsonNumber::from(f64::NAN));
+}
+
+#[test]
+pub fn number_overflowing_float_keeps_lexeme() {
+    for text in ["1e400", "-1e400", "1.5E+309"] {
+        let number = lex_number(text, LexerOptions::default());
+        assert_eq!(number.as_str_lossless(), Some(text));
+        assert!(number.as_f64().is_infinite());
+        assert_eq!(number.to_string(), text);
+    }
+    let value = Parser::parse_json("[1e400,-1e400]").unwrap();
+    let serialized = value.to_json_string().unwrap();
+    assert_eq!(serialized, "[1e400,-1e400]");
+    assert_eq!(value.to_string(), serialized);
+    assert_eq!(Parser::parse_json(&serialized).unwrap(), value);
+}
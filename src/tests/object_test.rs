@@ -0,0 +1,81 @@
+use crate::object::JsonObject;
+use crate::parser::{JsonValue, Parser};
+
+fn sample() -> JsonObject {
+    let mut object = JsonObject::new();
+    object.insert("zeta", JsonValue::Null);
+    object.insert("alpha", JsonValue::Boolean(true));
+    object.insert("mid", JsonValue::String("m".to_string()));
+    object
+}
+
+#[test]
+pub fn object_keeps_insertion_order() {
+    let object = sample();
+    let keys: Vec<&String> = object.keys().collect();
+    assert_eq!(keys, ["zeta", "alpha", "mid"]);
+    let values: Vec<&JsonValue> = object.values().collect();
+    assert_eq!(values[1], &JsonValue::Boolean(true));
+    let pairs: Vec<(&String, &JsonValue)> = object.iter().rev().collect();
+    assert_eq!(pairs[0].0, "mid");
+    assert_eq!(object.iter().len(), 3);
+}
+
+#[test]
+pub fn object_insert_replaces_in_place() {
+    let mut object = sample();
+    let old = object.insert("zeta", JsonValue::Boolean(false));
+    assert_eq!(old, Some(JsonValue::Null));
+    assert_eq!(object.len(), 3);
+    assert_eq!(object.keys().next().unwrap(), "zeta");
+    assert_eq!(object.get("zeta"), Some(&JsonValue::Boolean(false)));
+}
+
+#[test]
+pub fn object_get_mut_and_contains_key() {
+    let mut object = sample();
+    assert!(object.contains_key("alpha"));
+    assert!(!object.contains_key("beta"));
+    *object.get_mut("mid").unwrap() = JsonValue::Null;
+    assert_eq!(object.get("mid"), Some(&JsonValue::Null));
+    assert_eq!(object.get_mut("beta"), None);
+}
+
+#[test]
+pub fn object_remove_reindexes() {
+    let mut object = sample();
+    assert_eq!(object.remove("zeta"), Some(JsonValue::Null));
+    assert_eq!(object.remove("zeta"), None);
+    assert_eq!(object.len(), 2);
+    assert_eq!(object.get("mid"), Some(&JsonValue::String("m".to_string())));
+    let keys: Vec<&String> = object.keys().collect();
+    assert_eq!(keys, ["alpha", "mid"]);
+}
+
+#[test]
+pub fn object_parsed_duplicates_are_kept() {
+    let value = Parser::parse_json(r#"{"a": 1, "b": 2, "a": 3}"#).unwrap();
+    let JsonValue::Object(mut object) = value else {
+        panic!("expected an object");
+    };
+    assert_eq!(object.len(), 3);
+    assert_eq!(object.get("a").unwrap().to_string(), "1");
+    assert_eq!(object.remove("a").unwrap().to_string(), "1");
+    assert_eq!(object.len(), 1);
+    assert!(!object.contains_key("a"));
+}
+
+#[test]
+pub fn object_remove_keeps_lookups_consistent() {
+    let mut object: JsonObject = (0..100)
+        .map(|n| (n.to_string(), JsonValue::from(n)))
+        .collect();
+    for n in (0..100).step_by(3) {
+        assert_eq!(object.remove(&n.to_string()), Some(JsonValue::from(n)));
+    }
+    for n in 0..100 {
+        let expected = (n % 3 != 0).then(|| JsonValue::from(n));
+        assert_eq!(object.get(&n.to_string()), expected.as_ref());
+    }
+    assert_eq!(object.len(), 66);
+}
@@ -1,30 +1,35 @@
+use crate::error::{ParsedJsonError, ParserError};
 use crate::lexer::Lexer;
-use crate::parser::{JsonValue, Parser};
+use crate::object::JsonObject;
+use crate::parser::{DuplicateKeys, JsonValue, Parser, ParserOptions};
+use crate::token::{Span, Token};
 #[test]
 pub fn parse_valid_empty_delimitter_check() {
     let test_json = r#"{}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    assert_eq!(parser.parse().unwrap(), JsonValue::Object(Vec::new()))
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    assert_eq!(
+        parser.parse().unwrap(),
+        JsonValue::Object(JsonObject::new())
+    )
 }
 
 #[test]
 pub fn parse_valid_one_key_value_check() {
     let test_json = r#"{"name": "John"}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    let expected_value = JsonValue::Object(vec![(
-        "name".to_string(),
-        JsonValue::String("John".to_string()),
-    )]);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    let expected_value =
+        JsonValue::Object(vec![("name".to_string(), JsonValue::String("John".to_string()))].into());
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
 #[test]
 pub fn parse_valid_one_key_value_boolean_check() {
     let test_json = r#"{"name": true}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    let expected_value = JsonValue::Object(vec![("name".to_string(), JsonValue::Boolean(true))]);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    let expected_value =
+        JsonValue::Object(vec![("name".to_string(), JsonValue::Boolean(true))].into());
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
 
@@ -32,8 +37,9 @@ pub fn parse_valid_one_key_value_boolean_check() {
 pub fn parse_valid_one_key_value_number_check() {
     let test_json = r#"{"name": 10}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    let expected_value = JsonValue::Object(vec![("name".to_string(), JsonValue::Number(10.0))]);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    let expected_value =
+        JsonValue::Object(vec![("name".to_string(), JsonValue::Number(10_u64.into()))].into());
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
 
@@ -41,15 +47,15 @@ pub fn parse_valid_one_key_value_number_check() {
 pub fn parse_valid_one_key_value_null_check() {
     let test_json = r#"{"name": null}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    let expected_value = JsonValue::Object(vec![("name".to_string(), JsonValue::Null)]);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    let expected_value = JsonValue::Object(vec![("name".to_string(), JsonValue::Null)].into());
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
 #[test]
 pub fn parse_valid_empty_array_check() {
     let test_json = r#"[]"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
     assert_eq!(parser.parse().unwrap(), JsonValue::Array(Vec::new()));
 }
 
@@ -57,9 +63,9 @@ pub fn parse_valid_empty_array_check() {
 pub fn parse_valid_array_with_values_check() {
     let test_json = r#"[1, "two", true, null]"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
     let expected_value = JsonValue::Array(vec![
-        JsonValue::Number(1.0),
+        JsonValue::Number(1_u64.into()),
         JsonValue::String("two".to_string()),
         JsonValue::Boolean(true),
         JsonValue::Null,
@@ -71,14 +77,20 @@ pub fn parse_valid_array_with_values_check() {
 pub fn parse_valid_nested_object_check() {
     let test_json = r#"{"name": {"first": "John", "last": "Doe"}}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    let expected_value = JsonValue::Object(vec![(
-        "name".to_string(),
-        JsonValue::Object(vec![
-            ("first".to_string(), JsonValue::String("John".to_string())),
-            ("last".to_string(), JsonValue::String("Doe".to_string())),
-        ]),
-    )]);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    let expected_value = JsonValue::Object(
+        vec![(
+            "name".to_string(),
+            JsonValue::Object(
+                vec![
+                    ("first".to_string(), JsonValue::String("John".to_string())),
+                    ("last".to_string(), JsonValue::String("Doe".to_string())),
+                ]
+                .into(),
+            ),
+        )]
+        .into(),
+    );
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
 
@@ -87,16 +99,10 @@ pub fn parse_valid_nested_object_check() {
 pub fn parse_valid_array_of_objects_check() {
     let test_json = r#"[{"name": "John"}, {"name": "Jane"}]"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
     let expected_value = JsonValue::Array(vec![
-        JsonValue::Object(vec![(
-            "name".to_string(),
-            JsonValue::String("John".to_string()),
-        )]),
-        JsonValue::Object(vec![(
-            "name".to_string(),
-            JsonValue::String("Jane".to_string()),
-        )]),
+        JsonValue::Object(vec![("name".to_string(), JsonValue::String("John".to_string()))].into()),
+        JsonValue::Object(vec![("name".to_string(), JsonValue::String("Jane".to_string()))].into()),
     ]);
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
@@ -105,16 +111,13 @@ pub fn parse_valid_array_of_objects_check() {
 pub fn parse_valid_mixed_array_check() {
     let test_json = r#"[{"key": "value"}, [1, 2, 3], true]"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
     let expected_value = JsonValue::Array(vec![
-        JsonValue::Object(vec![(
-            "key".to_string(),
-            JsonValue::String("value".to_string()),
-        )]),
+        JsonValue::Object(vec![("key".to_string(), JsonValue::String("value".to_string()))].into()),
         JsonValue::Array(vec![
-            JsonValue::Number(1.0),
-            JsonValue::Number(2.0),
-            JsonValue::Number(3.0),
+            JsonValue::Number(1_u64.into()),
+            JsonValue::Number(2_u64.into()),
+            JsonValue::Number(3_u64.into()),
         ]),
         JsonValue::Boolean(true),
     ]);
@@ -125,12 +128,260 @@ pub fn parse_valid_mixed_array_check() {
 pub fn parse_valid_object_with_multiple_data_types_check() {
     let test_json = r#"{"string": "value", "number": 42, "boolean": false, "null_value": null}"#;
     let mut lexer = Lexer::new(test_json);
-    let mut parser = Parser::new(lexer.lex().unwrap());
-    let expected_value = JsonValue::Object(vec![
-        ("string".to_string(), JsonValue::String("value".to_string())),
-        ("number".to_string(), JsonValue::Number(42.0)),
-        ("boolean".to_string(), JsonValue::Boolean(false)),
-        ("null_value".to_string(), JsonValue::Null),
-    ]);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    let expected_value = JsonValue::Object(
+        vec![
+            ("string".to_string(), JsonValue::String("value".to_string())),
+            ("number".to_string(), JsonValue::Number(42_u64.into())),
+            ("boolean".to_string(), JsonValue::Boolean(false)),
+            ("null_value".to_string(), JsonValue::Null),
+        ]
+        .into(),
+    );
     assert_eq!(parser.parse().unwrap(), expected_value);
 }
+
+#[test]
+pub fn parse_invalid_token_reports_span() {
+    let test_json = "{\n  \"key\" \"value\"\n}";
+    let mut lexer = Lexer::new(test_json);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    match parser.parse() {
+        Err(ParserError::ExpectedNameSeperator(Token::ValueString(found), span)) => {
+            assert_eq!(found, "value");
+            assert_eq!(span, Span::new(10, 17, 2, 9));
+        }
+        other => panic!("expected missing colon error, got {:?}", other),
+    }
+}
+
+#[test]
+pub fn parse_lenient_seperators_check() {
+    let test_json = r#"{"a": [1 2, 3,] "b": true,}"#;
+    let mut lexer = Lexer::new(test_json);
+    let options = ParserOptions {
+        lenient_seperators: true,
+        ..ParserOptions::default()
+    };
+    let mut parser = Parser::with_options(lexer.lex().unwrap(), lexer.end(), options);
+    let expected_value = JsonValue::Object(
+        vec![
+            (
+                "a".to_string(),
+                JsonValue::Array(vec![
+                    JsonValue::Number(1_u64.into()),
+                    JsonValue::Number(2_u64.into()),
+                    JsonValue::Number(3_u64.into()),
+                ]),
+            ),
+            ("b".to_string(), JsonValue::Boolean(true)),
+        ]
+        .into(),
+    );
+    assert_eq!(parser.parse().unwrap(), expected_value);
+}
+
+#[test]
+pub fn parse_strict_trailing_seperator_reports_span() {
+    let test_json = "[1, 2,\n]";
+    let mut lexer = Lexer::new(test_json);
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    match parser.parse() {
+        Err(ParserError::TrailingSeperator(Token::EndArray, span)) => {
+            assert_eq!(span, Span::new(5, 6, 1, 6));
+        }
+        other => panic!("expected trailing seperator error, got {:?}", other),
+    }
+}
+
+#[test]
+pub fn parse_valid_scalar_roots_check() {
+    for (test_json, expected_value) in [
+        (r#""ok""#, JsonValue::String("ok".to_string())),
+        ("42", JsonValue::Number(42_u64.into())),
+        ("-1.5", JsonValue::Number((-1.5_f64).into())),
+        ("true", JsonValue::Boolean(true)),
+        ("false", JsonValue::Boolean(false)),
+        ("null", JsonValue::Null),
+        (" \n 7 \n", JsonValue::Number(7_u64.into())),
+    ] {
+        assert_eq!(Parser::parse_json(test_json).unwrap(), expected_value);
+    }
+}
+
+#[test]
+pub fn parse_invalid_empty_input_check() {
+    for test_json in ["", "   ", "\n \n"] {
+        let mut lexer = Lexer::new(test_json);
+        let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+        assert!(matches!(
+            parser.parse(),
+            Err(ParserError::UnexpectedEndOfInput(_))
+        ));
+    }
+}
+
+#[test]
+pub fn parse_invalid_start_check() {
+    for test_json in ["]", "}", ":", ","] {
+        let mut lexer = Lexer::new(test_json);
+        let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+        assert!(matches!(
+            parser.parse(),
+            Err(ParserError::UnexpectedStart(_, _))
+        ));
+    }
+}
+
+#[test]
+pub fn parse_valid_stream_check() {
+    let test_json = "{\"a\": 1} {\"b\": 2}\n[true] \"ok\" 3";
+    let expected_value = vec![
+        JsonValue::Object(vec![("a".to_string(), JsonValue::Number(1_u64.into()))].into()),
+        JsonValue::Object(vec![("b".to_string(), JsonValue::Number(2_u64.into()))].into()),
+        JsonValue::Array(vec![JsonValue::Boolean(true)]),
+        JsonValue::String("ok".to_string()),
+        JsonValue::Number(3_u64.into()),
+    ];
+    assert_eq!(
+        Parser::parse_json_stream(test_json).unwrap(),
+        expected_value
+    );
+    assert_eq!(Parser::parse_json_stream(" ").unwrap(), Vec::new());
+}
+
+#[test]
+pub fn parse_invalid_stream_check() {
+    let mut lexer = Lexer::new("{} ] {}");
+    let mut parser = Parser::new(lexer.lex().unwrap(), lexer.end());
+    assert!(matches!(
+        parser.parse_stream(),
+        Err(ParserError::UnexpectedStart(Token::EndArray, _))
+    ));
+}
+
+fn parse_with_duplicates(
+    json: &str,
+    duplicate_keys: DuplicateKeys,
+) -> Result<JsonValue, ParserError> {
+    let options = ParserOptions {
+        duplicate_keys,
+        ..ParserOptions::default()
+    };
+    let mut lexer = Lexer::new(json);
+    Parser::with_options(lexer.lex().unwrap(), lexer.end(), options).parse()
+}
+
+#[test]
+pub fn parse_duplicate_keys_policies() {
+    let json = r#"{"a": 1, "b": 2, "a": 3}"#;
+    let keep_all = parse_with_duplicates(json, DuplicateKeys::KeepAll).unwrap();
+    assert_eq!(keep_all.to_string(), r#"{"a":1,"b":2,"a":3}"#);
+    assert_eq!(keep_all["a"].to_string(), "1");
+
+    let first_wins = parse_with_duplicates(json, DuplicateKeys::FirstWins).unwrap();
+    assert_eq!(first_wins.to_string(), r#"{"a":1,"b":2}"#);
+
+    let last_wins = parse_with_duplicates(json, DuplicateKeys::LastWins).unwrap();
+    assert_eq!(last_wins.to_string(), r#"{"a":3,"b":2}"#);
+}
+
+#[test]
+pub fn parse_duplicate_keys_error_reports_both_spans() {
+    let json = "{\"a\": 1,\n \"a\": {\"a\": 2}}";
+    match parse_with_duplicates(json, DuplicateKeys::Error) {
+        Err(ParserError::DuplicateKey(key, first, second)) => {
+            assert_eq!(key, "a");
+            assert_eq!((first.line, first.column), (1, 2));
+            assert_eq!((second.line, second.column), (2, 2));
+        }
+        other => panic!("expected a duplicate key error, got {:?}", other),
+    }
+    // the same key in a nested object is not a duplicate
+    assert!(parse_with_duplicates(r#"{"a": {"a": 1}}"#, DuplicateKeys::Error).is_ok());
+}
+
+#[test]
+pub fn parse_indexing_chains_through_missing_data() {
+    let value = Parser::parse_json(r#"{"a": {"b": [1, 2, 3, {"c": true}]}, "s": "x"}"#).unwrap();
+    assert_eq!(value["a"]["b"][3]["c"], JsonValue::Boolean(true));
+    assert!(value["missing"]["b"][3].is_null());
+    assert!(value["a"]["b"][10].is_null());
+    assert!(value["s"]["key"][0].is_null());
+    assert!(value[0].is_null());
+    // the fallible accessors still report what went wrong
+    assert!(matches!(
+        value.get("missing"),
+        Err(ParsedJsonError::KeyNotFound(_))
+    ));
+    assert!(matches!(
+        value["a"]["b"].index(10),
+        Err(ParsedJsonError::IndexOutOfBounds(10))
+    ));
+}
+
+#[test]
+pub fn parse_typed_accessors() {
+    let value = Parser::parse_json(
+        r#"{"s": "x", "i": -3, "u": 18446744073709551615, "f": 1.5, "b": true, "n": null, "a": [1]}"#,
+    )
+    .unwrap();
+    assert_eq!(value["s"].as_str(), Some("x"));
+    assert_eq!(value["i"].as_i64(), Some(-3));
+    assert_eq!(value["i"].as_u64(), None);
+    assert_eq!(value["u"].as_u64(), Some(u64::MAX));
+    assert_eq!(value["u"].as_i64(), None);
+    assert_eq!(value["f"].as_f64(), Some(1.5));
+    assert_eq!(value["f"].as_i64(), None);
+    assert_eq!(value["i"].as_f64(), Some(-3.0));
+    assert_eq!(value["b"].as_bool(), Some(true));
+    assert_eq!(value["a"].as_array().map(Vec::len), Some(1));
+    assert_eq!(value.as_object().map(|obj| obj.len()), Some(7));
+    assert_eq!(value["s"].as_bool(), None);
+    assert_eq!(value["n"].as_str(), None);
+    assert!(value["a"].as_object().is_none());
+
+    assert!(value.is_object() && value["a"].is_array() && value["s"].is_string());
+    assert!(value["f"].is_number() && value["b"].is_boolean() && value["n"].is_null());
+    assert!(!value["n"].is_object() && !value["s"].is_number());
+}
+
+#[test]
+pub fn parse_unexpected_type_names_both_types() {
+    let value = Parser::parse_json(r#"{"n": 1, "a": []}"#).unwrap();
+    match value["n"].get_string() {
+        Err(ParsedJsonError::UnexpectedType { expected, found }) => {
+            assert_eq!((expected, found), ("string", "number"));
+        }
+        other => panic!("expected a type error, got {:?}", other),
+    }
+    assert_eq!(
+        value["a"].get("key").unwrap_err().to_string(),
+        "Expected object, found array"
+    );
+    assert_eq!(
+        value.index(0).unwrap_err().to_string(),
+        "Expected array, found object"
+    );
+}
+
+fn end_of_input_span(json: &str) -> Span {
+    let mut lexer = Lexer::new(json);
+    let tokens = lexer.lex().unwrap();
+    match Parser::new(tokens, lexer.end()).parse() {
+        Err(ParserError::UnexpectedEndOfInput(span)) => span,
+        other => panic!("expected end of input, got {:?}", other),
+    }
+}
+
+#[test]
+pub fn parse_end_of_input_span_is_where_the_input_ends() {
+    let span = end_of_input_span(r#"{"a": "Bobbbbbb""#);
+    assert_eq!((span.start, span.line, span.column), (16, 1, 17));
+    let span = end_of_input_span("{\"a\": 1   \n\n   ");
+    assert_eq!((span.start, span.line, span.column), (15, 3, 4));
+    let span = end_of_input_span("  \n ");
+    assert_eq!((span.start, span.line, span.column), (4, 2, 2));
+    let span = end_of_input_span("[\"é\"");
+    assert_eq!((span.start, span.line, span.column), (5, 1, 5));
+}
@@ -0,0 +1,122 @@
+use crate::parser::{JsonValue, Parser};
+use crate::pretty::{FormatOptions, Indent, Newline};
+
+fn sample() -> JsonValue {
+    Parser::parse_json(
+        r#"{"name":"app","ports":[80,443],"env":{},"tags":[],"db":{"host":"x","pool":[1,2]}}"#,
+    )
+    .unwrap()
+}
+
+#[test]
+pub fn pretty_default_layout() {
+    assert_eq!(
+        sample()
+            .to_pretty_string(&FormatOptions::default())
+            .unwrap(),
+        r#"{
+  "name": "app",
+  "ports": [
+    80,
+    443
+  ],
+  "env": {},
+  "tags": [],
+  "db": {
+    "host": "x",
+    "pool": [
+      1,
+      2
+    ]
+  }
+}"#
+    );
+}
+
+#[test]
+pub fn pretty_tabs_crlf_and_no_space() {
+    let options = FormatOptions {
+        indent: Indent::Tab,
+        newline: Newline::CrLf,
+        space_after_colon: false,
+        ..FormatOptions::default()
+    };
+    let value = Parser::parse_json(r#"{"a":[1],"b":{"c":null}}"#).unwrap();
+    assert_eq!(
+        value.to_pretty_string(&options).unwrap(),
+        "{\r\n\t\"a\":[\r\n\t\t1\r\n\t],\r\n\t\"b\":{\r\n\t\t\"c\":null\r\n\t}\r\n}"
+    );
+}
+
+#[test]
+pub fn pretty_expanded_empty_containers() {
+    let options = FormatOptions {
+        indent: Indent::Spaces(4),
+        inline_empty: false,
+        ..FormatOptions::default()
+    };
+    let value = Parser::parse_json(r#"{"a":[],"b":{}}"#).unwrap();
+    assert_eq!(
+        value.to_pretty_string(&options).unwrap(),
+        "{\n    \"a\": [\n    ],\n    \"b\": {\n    }\n}"
+    );
+}
+
+#[test]
+pub fn pretty_max_line_width() {
+    let options = FormatOptions {
+        max_line_width: 24,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        sample().to_pretty_string(&options).unwrap(),
+        r#"{
+  "name": "app",
+  "ports": [80, 443],
+  "env": {},
+  "tags": [],
+  "db": {
+    "host": "x",
+    "pool": [1, 2]
+  }
+}"#
+    );
+
+    // `  "ports": [80, 443],` is 21 columns including its comma
+    let options = FormatOptions {
+        max_line_width: 20,
+        ..FormatOptions::default()
+    };
+    let pretty = sample().to_pretty_string(&options).unwrap();
+    assert!(pretty.contains("\"ports\": [\n"));
+    assert!(pretty.contains("\"pool\": [1, 2]\n"));
+
+    let options = FormatOptions {
+        max_line_width: 200,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        sample().to_pretty_string(&options).unwrap(),
+        r#"{"name": "app", "ports": [80, 443], "env": {}, "tags": [], "db": {"host": "x", "pool": [1, 2]}}"#
+    );
+}
+
+#[test]
+pub fn pretty_round_trips_and_writes() {
+    let value = sample();
+    let pretty = value.to_pretty_string(&FormatOptions::default()).unwrap();
+    assert_eq!(Parser::parse_json(&pretty).unwrap(), value);
+
+    let mut buffer = Vec::new();
+    value
+        .write_pretty(&mut buffer, &FormatOptions::default())
+        .unwrap();
+    assert_eq!(buffer, pretty.as_bytes());
+
+    assert_eq!(
+        JsonValue::String("x".to_string())
+            .to_pretty_string(&FormatOptions::default())
+            .unwrap(),
+        r#""x""#
+    );
+}
@@ -0,0 +1,95 @@
+use crate::error::SerializerError;
+use crate::lexer::{Lexer, LexerOptions};
+use crate::parser::{JsonValue, Parser};
+
+#[test]
+pub fn serialize_compact_output() {
+    let test_json = r#"{ "name" : "John", "age": 30, "tags": [ "a", true, null ], "nested": {} }"#;
+    let value = Parser::parse_json(test_json).unwrap();
+    assert_eq!(
+        value.to_json_string().unwrap(),
+        r#"{"name":"John","age":30,"tags":["a",true,null],"nested":{}}"#
+    );
+    assert_eq!(
+        value.to_string(),
+        r#"{"name":"John","age":30,"tags":["a",true,null],"nested":{}}"#
+    );
+}
+
+#[test]
+pub fn serialize_preserves_object_order() {
+    let value = JsonValue::Object(
+        vec![
+            ("z".to_string(), JsonValue::Number(1_u64.into())),
+            ("a".to_string(), JsonValue::Number(2_u64.into())),
+            ("m".to_string(), JsonValue::Array(Vec::new())),
+        ]
+        .into(),
+    );
+    assert_eq!(value.to_json_string().unwrap(), r#"{"z":1,"a":2,"m":[]}"#);
+}
+
+#[test]
+pub fn serialize_escapes_strings() {
+    let value =
+        JsonValue::String("quote\" back\\ slash/ \u{8}\u{c}\n\r\t \u{1} \u{1f} é 😀".to_string());
+    assert_eq!(
+        value.to_json_string().unwrap(),
+        r#""quote\" back\\ slash/ \b\f\n\r\t \u0001 \u001f é 😀""#
+    );
+}
+
+#[test]
+pub fn serialize_refuses_non_finite_numbers() {
+    for number in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let value = JsonValue::Array(vec![JsonValue::Number(number.into())]);
+        assert!(matches!(
+            value.to_json_string(),
+            Err(SerializerError::NonFiniteNumber(_))
+        ));
+        let mut buffer = Vec::new();
+        assert!(value.write_json(&mut buffer).is_err());
+    }
+}
+
+#[test]
+pub fn serialize_display_writes_non_finite_numbers_as_null() {
+    let value = JsonValue::Array(vec![
+        JsonValue::Number(f64::NAN.into()),
+        JsonValue::Number(f64::INFINITY.into()),
+        JsonValue::Number(1.5.into()),
+    ]);
+    assert_eq!(value.to_string(), "[null,null,1.5]");
+    assert_eq!(format!("{}", JsonValue::Number(f64::NAN.into())), "null");
+}
+
+#[test]
+pub fn serialize_write_json() {
+    let value = Parser::parse_json(r#"[1, -2.5, "x"]"#).unwrap();
+    let mut buffer = Vec::new();
+    value.write_json(&mut buffer).unwrap();
+    assert_eq!(buffer, br#"[1,-2.5,"x"]"#);
+}
+
+#[test]
+pub fn serialize_round_trips() {
+    let test_json =
+        r#"{"a":[1,-2,0.5,1e300,2.0,1e2,-5.0,"\u0000\"\\",{"b":null}],"c":false,"":""}"#;
+    let value = Parser::parse_json(test_json).unwrap();
+    let serialized = value.to_json_string().unwrap();
+    assert_eq!(Parser::parse_json(&serialized).unwrap(), value);
+}
+
+#[test]
+pub fn serialize_lossless_numbers_byte_for_byte() {
+    let test_json = r#"[1.50,-0,1E+2,0.1000000000000000000000001,123456789012345678901234567890]"#;
+    let options = LexerOptions {
+        lossless_numbers: true,
+        ..LexerOptions::default()
+    };
+    let mut lexer = Lexer::with_options(test_json, options);
+    let value = Parser::new(lexer.lex().unwrap(), lexer.end())
+        .parse()
+        .unwrap();
+    assert_eq!(value.to_json_string().unwrap(), test_json);
+}
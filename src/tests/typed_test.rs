@@ -0,0 +1,147 @@
+use crate::error::{FromJsonError, ParsedJsonError, PathSegment};
+use crate::parser::{JsonValue, Parser};
+use crate::typed::{FromJson, ToJson};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, PartialEq)]
+struct User {
+    name: String,
+    email: String,
+    age: Option<u8>,
+}
+
+impl ToJson for User {
+    fn to_json(&self) -> JsonValue {
+        json!({"name": self.name.as_str(), "email": self.email.as_str(), "age": self.age})
+    }
+}
+
+impl FromJson for User {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(User {
+            name: value.field("name")?,
+            email: value.field("email")?,
+            age: value.field("age")?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Team {
+    users: Vec<User>,
+}
+
+impl FromJson for Team {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(Team {
+            users: value.field("users")?,
+        })
+    }
+}
+
+#[test]
+pub fn typed_round_trip_of_std_types() {
+    let mut scores = BTreeMap::new();
+    scores.insert("a".to_string(), vec![(1_u8, 'x'), (2, 'y')]);
+    let value = scores.to_json();
+    assert_eq!(value.to_string(), r#"{"a":[[1,"x"],[2,"y"]]}"#);
+    assert_eq!(
+        BTreeMap::<String, Vec<(u8, char)>>::from_json(&value).unwrap(),
+        scores
+    );
+
+    let mut map = HashMap::new();
+    map.insert("k".to_string(), Some(Box::new(-1.5_f64)));
+    map.insert("n".to_string(), None);
+    let value = map.to_json();
+    assert_eq!(
+        HashMap::<String, Option<Box<f64>>>::from_json(&value).unwrap(),
+        map
+    );
+
+    let tuple = (true, "s".to_string(), -7_i64, 3_u128);
+    assert_eq!(tuple.to_json().to_string(), r#"[true,"s",-7,3]"#);
+    assert_eq!(
+        <(bool, String, i64, u128)>::from_json(&tuple.to_json()).unwrap(),
+        tuple
+    );
+    assert_eq!("text".to_json(), JsonValue::String("text".to_string()));
+    assert_eq!([1_i32, 2][..].to_json().to_string(), "[1,2]");
+}
+
+#[test]
+pub fn typed_struct_round_trip() {
+    let user = User {
+        name: "Ann".to_string(),
+        email: "ann@example.com".to_string(),
+        age: None,
+    };
+    let value = user.to_json();
+    assert_eq!(
+        value.to_string(),
+        r#"{"name":"Ann","email":"ann@example.com","age":null}"#
+    );
+    assert_eq!(User::from_json(&value).unwrap(), user);
+    // a missing optional member reads as `None`
+    let value = json!({"name": "Bo", "email": "bo@example.com"});
+    assert_eq!(User::from_json(&value).unwrap().age, None);
+}
+
+#[test]
+pub fn typed_errors_carry_the_path() {
+    let value = Parser::parse_json(
+        r#"{"users": [
+            {"name": "a", "email": "a@x"},
+            {"name": "b", "email": "b@x"},
+            {"name": "c", "email": "c@x", "age": 30},
+            {"name": "d", "email": 42}
+        ]}"#,
+    )
+    .unwrap();
+    let err = Team::from_json(&value).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ".users[3].email: Expected string, found number"
+    );
+    let path: Vec<&PathSegment> = err.path().collect();
+    assert_eq!(
+        path,
+        [
+            &PathSegment::Key("users".to_string()),
+            &PathSegment::Index(3),
+            &PathSegment::Key("email".to_string()),
+        ]
+    );
+    assert!(matches!(
+        err.error(),
+        ParsedJsonError::UnexpectedType { .. }
+    ));
+}
+
+#[test]
+pub fn typed_errors_for_missing_members_and_ranges() {
+    let value = json!({"users": [{"name": "a"}]});
+    assert_eq!(
+        Team::from_json(&value).unwrap_err().to_string(),
+        ".users[0]: Key 'email' not found in JSON object"
+    );
+    let value = json!({"users": [{"name": "a", "email": "a@x", "age": 300}]});
+    assert_eq!(
+        Team::from_json(&value).unwrap_err().to_string(),
+        ".users[0].age: Number 300 does not fit in u8"
+    );
+    assert_eq!(
+        <(u8, u8)>::from_json(&json!([1, 2, 3]))
+            .unwrap_err()
+            .to_string(),
+        "Expected 2 elements, found 3"
+    );
+    assert_eq!(
+        char::from_json(&json!("ab")).unwrap_err().to_string(),
+        "Expected single character string, found string"
+    );
+    assert_eq!(
+        User::from_json(&json!([])).unwrap_err().to_string(),
+        "Expected object, found array"
+    );
+}
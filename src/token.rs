@@ -1,3 +1,5 @@
+use crate::number::JsonNumber;
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum Token {
     BeginArray,     // [
@@ -6,8 +8,54 @@ pub enum Token {
     EndObject,      // }
     NameSeperator,  // :
     ValueSeperator, // ,
-    ValueNumber(f64),
+    ValueNumber(JsonNumber),
     ValueBoolean(bool),
     ValueString(String),
     ValueNil,
 }
+
+/// Location of a piece of input. `start` and `end` are byte offsets into the
+/// source, `line` and `column` are 1-based and point at `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Span {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+}
+
+impl Default for Span {
+    fn default() -> Self {
+        Span::new(0, 0, 1, 1)
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A token together with the span of input it was lexed from.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+impl SpannedToken {
+    pub fn new(token: Token, span: Span) -> Self {
+        SpannedToken { token, span }
+    }
+}
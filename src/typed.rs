@@ -0,0 +1,252 @@
+use crate::{
+    error::{FromJsonError, ParsedJsonError},
+    object::JsonObject,
+    parser::JsonValue,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Types that can be written as a `JsonValue`.
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+/// Types that can be read back from a `JsonValue`. Errors carry the path of
+/// the failing value, so implementations for containers should mark nested
+/// errors with `FromJsonError::at_key` or `at_index`; `JsonValue::field`
+/// does that for struct members.
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError>;
+}
+
+impl JsonValue {
+    /// Reads the member `key` of an object as `T`. A missing member reads as
+    /// `null`, so it is accepted for `Option` fields and reported as
+    /// `KeyNotFound` otherwise.
+    pub fn field<T: FromJson>(&self, key: &str) -> Result<T, FromJsonError> {
+        let object = object_of(self)?;
+        match object.get(key) {
+            Some(value) => T::from_json(value).map_err(|err| err.at_key(key)),
+            None => T::from_json(&JsonValue::Null)
+                .map_err(|_| ParsedJsonError::KeyNotFound(key.to_string()).into()),
+        }
+    }
+}
+
+fn object_of(value: &JsonValue) -> Result<&JsonObject, FromJsonError> {
+    value
+        .as_object()
+        .ok_or_else(|| value.unexpected_type("object").into())
+}
+
+fn array_of(value: &JsonValue) -> Result<&Vec<JsonValue>, FromJsonError> {
+    value
+        .as_array()
+        .ok_or_else(|| value.unexpected_type("array").into())
+}
+
+impl ToJson for JsonValue {
+    fn to_json(&self) -> JsonValue {
+        self.clone()
+    }
+}
+
+impl FromJson for JsonValue {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(value.clone())
+    }
+}
+
+// scalars reuse the `From` and `TryFrom<&JsonValue>` conversions
+macro_rules! json_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl ToJson for $ty {
+                fn to_json(&self) -> JsonValue {
+                    JsonValue::from(*self)
+                }
+            }
+
+            impl FromJson for $ty {
+                fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+                    Ok(<$ty>::try_from(value)?)
+                }
+            }
+        )*
+    };
+}
+
+json_scalar!(bool, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl ToJson for char {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
+
+/// Accepts a string of exactly one character.
+impl FromJson for char {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        let mut chars = value.get_string()?.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => Ok(ch),
+            _ => Err(ParsedJsonError::UnexpectedType {
+                expected: "single character string",
+                found: "string",
+            }
+            .into()),
+        }
+    }
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::from(self)
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        Ok(String::try_from(value)?)
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> JsonValue {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for Box<T> {
+    fn to_json(&self) -> JsonValue {
+        (**self).to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        T::from_json(value).map(Box::new)
+    }
+}
+
+/// `None` is written as `null`.
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue {
+        match *self {
+            Some(ref value) => value.to_json(),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+/// `null` reads as `None`.
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        match *value {
+            JsonValue::Null => Ok(None),
+            _ => T::from_json(value).map(Some),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_slice().to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        array_of(value)?
+            .iter()
+            .enumerate()
+            .map(|(index, element)| T::from_json(element).map_err(|err| err.at_index(index)))
+            .collect()
+    }
+}
+
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(
+            self.iter()
+                .map(|(k, v)| (k.as_str(), v.to_json()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        object_of(value)?
+            .iter()
+            .map(|(key, member)| {
+                let member = T::from_json(member).map_err(|err| err.at_key(key))?;
+                Ok((key.clone(), member))
+            })
+            .collect()
+    }
+}
+
+impl<T: ToJson> ToJson for BTreeMap<String, T> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(
+            self.iter()
+                .map(|(k, v)| (k.as_str(), v.to_json()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: FromJson> FromJson for BTreeMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+        object_of(value)?
+            .iter()
+            .map(|(key, member)| {
+                let member = T::from_json(member).map_err(|err| err.at_key(key))?;
+                Ok((key.clone(), member))
+            })
+            .collect()
+    }
+}
+
+/// Tuples are written as arrays and must be read from arrays of the same
+/// length.
+macro_rules! json_tuple {
+    ($len:expr => $($index:tt $name:ident),+) => {
+        impl<$($name: ToJson),+> ToJson for ($($name,)+) {
+            fn to_json(&self) -> JsonValue {
+                JsonValue::Array(vec![$(self.$index.to_json()),+])
+            }
+        }
+
+        impl<$($name: FromJson),+> FromJson for ($($name,)+) {
+            fn from_json(value: &JsonValue) -> Result<Self, FromJsonError> {
+                let array = array_of(value)?;
+                if array.len() != $len {
+                    return Err(ParsedJsonError::LengthMismatch($len, array.len()).into());
+                }
+                Ok(($(
+                    $name::from_json(&array[$index]).map_err(|err| err.at_index($index))?,
+                )+))
+            }
+        }
+    };
+}
+
+json_tuple!(1 => 0 A);
+json_tuple!(2 => 0 A, 1 B);
+json_tuple!(3 => 0 A, 1 B, 2 C);
+json_tuple!(4 => 0 A, 1 B, 2 C, 3 D);
+json_tuple!(5 => 0 A, 1 B, 2 C, 3 D, 4 E);
+json_tuple!(6 => 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);